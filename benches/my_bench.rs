@@ -24,6 +24,24 @@ fn index_item_decode(c: &mut Criterion) {
         });
     });
 
+    let positions: Vec<u128> = (0..len).step_by(3).collect();
+
+    c.bench_function("bench set loop", |b| {
+        let mut bf = BitFlag::<u128>::new();
+        b.iter(|| {
+            for pos in black_box(&positions) {
+                bf.set(*pos, true);
+            }
+        });
+    });
+
+    c.bench_function("bench set positions", |b| {
+        let mut bf = BitFlag::<u128>::new();
+        b.iter(|| {
+            bf.set_positions(black_box(&positions));
+        });
+    });
+
     c.bench_function("bench get", |b| {
         b.iter(|| {
             for i in 0..len {
@@ -39,6 +57,10 @@ fn index_item_decode(c: &mut Criterion) {
             }
         });
     });
+
+    c.bench_function("bench len", |b| {
+        b.iter(|| black_box(bmap).len());
+    });
 }
 
 criterion_group!(benches, index_item_decode);
@@ -1,9 +1,14 @@
 use std::{
-    fmt::{Binary, Display},
+    fmt::{Binary, Display, LowerHex, Octal, UpperHex},
+    mem,
+    num::ParseIntError,
     ops::{Add, BitAnd, BitOr, BitXor, Not, Shl, Shr},
 };
 
 /// Trait defining everything that can be used as bitflag base type.
+///
+/// It's implemented for `u8`, `u16`, `u32`, `u64`, `u128`, `usize`, `i16`, `i32`, `i64`, `i128`
+/// and `isize`. Other types have to implement it themselves.
 pub trait BitflagAble:
     BitOr<Output = Self>
     + PartialOrd
@@ -16,26 +21,298 @@ pub trait BitflagAble:
     + Default
     + Display
     + Binary
+    + LowerHex
+    + UpperHex
+    + Octal
     + Copy
     + PartialEq
     + From<u8>
 {
+    /// Returns the number of ones in the binary representation of `self`. The default checks
+    /// every bit, the primitives use their native popcount instead
+    #[inline]
+    fn count_ones(self) -> u32 {
+        let one = Self::from(1u8);
+        (0..mem::size_of::<Self>() * 8)
+            .filter(|i| (self >> Self::from(*i as u8)) & one == one)
+            .count() as u32
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of `self`
+    fn trailing_zeros(self) -> u32;
+
+    /// Returns the number of leading zeros in the binary representation of `self`
+    fn leading_zeros(self) -> u32;
+
+    /// Reverses the order of bits in `self`
+    fn reverse_bits(self) -> Self;
+
+    /// Shifts the bits to the left by `n`, wrapping the truncated bits to the end
+    fn rotate_left(self, n: u32) -> Self;
+
+    /// Shifts the bits to the right by `n`, wrapping the truncated bits to the beginning
+    fn rotate_right(self, n: u32) -> Self;
+
+    /// Reverses the byte order of `self`
+    fn swap_bytes(self) -> Self;
+
+    /// Converts `self` to big endian from the target's endianness
+    fn to_be(self) -> Self;
+
+    /// Converts `self` to little endian from the target's endianness
+    fn to_le(self) -> Self;
+
+    /// Returns the least significant byte of `self`
+    fn low_byte(self) -> u8;
+
+    /// Parses the digits in `src` with the given `radix`. Signed types accept all values of their
+    /// unsigned counterpart, so every bit can be set
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError>;
+
+    /// Converts `self` to usize, truncating values that don't fit
+    fn to_usize(self) -> usize;
+
+    /// Converts the position `pos` to `Self`, truncating values that don't fit
+    fn from_usize(pos: usize) -> Self;
+
+    /// Adds `rhs` to `self` as unsigned numbers, returning `None` on overflow
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Adds `rhs` to `self` as unsigned numbers, wrapping around on overflow
+    fn wrapping_add(self, rhs: Self) -> Self;
+
+    /// Adds `rhs` to `self` as unsigned numbers, saturating at all bits set
+    fn saturating_add(self, rhs: Self) -> Self;
 }
 
-impl<U> BitflagAble for U where
-    U: BitOr<Output = U>
-        + PartialOrd
-        + BitAnd<Output = U>
-        + BitXor<Output = U>
-        + Shl<Output = U>
-        + Shr<Output = Self>
-        + Not<Output = U>
-        + Add<Output = Self>
-        + Default
-        + Display
-        + Binary
-        + Copy
-        + PartialEq
-        + From<u8>
-{
+macro_rules! impl_bitflagable {
+    ($($t:ty => $unsigned:ty),*) => {
+        $(
+            impl BitflagAble for $t {
+                #[inline]
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                #[inline]
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+
+                #[inline]
+                fn leading_zeros(self) -> u32 {
+                    <$t>::leading_zeros(self)
+                }
+
+                #[inline]
+                fn reverse_bits(self) -> Self {
+                    <$t>::reverse_bits(self)
+                }
+
+                #[inline]
+                fn rotate_left(self, n: u32) -> Self {
+                    <$t>::rotate_left(self, n)
+                }
+
+                #[inline]
+                fn rotate_right(self, n: u32) -> Self {
+                    <$t>::rotate_right(self, n)
+                }
+
+                #[inline]
+                fn swap_bytes(self) -> Self {
+                    <$t>::swap_bytes(self)
+                }
+
+                #[inline]
+                fn to_be(self) -> Self {
+                    <$t>::to_be(self)
+                }
+
+                #[inline]
+                fn to_le(self) -> Self {
+                    <$t>::to_le(self)
+                }
+
+                #[inline]
+                fn low_byte(self) -> u8 {
+                    self as u8
+                }
+
+                #[inline]
+                fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$unsigned>::from_str_radix(src, radix).map(|v| v as $t)
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+
+                #[inline]
+                fn from_usize(pos: usize) -> Self {
+                    pos as $t
+                }
+
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    (self as $unsigned).checked_add(rhs as $unsigned).map(|v| v as $t)
+                }
+
+                #[inline]
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    (self as $unsigned).wrapping_add(rhs as $unsigned) as $t
+                }
+
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    (self as $unsigned).saturating_add(rhs as $unsigned) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_bitflagable!(
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    u128 => u128,
+    usize => usize,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BitFlag;
+    use std::{fmt, ops};
+
+    /// Base type defined outside of the primitives, relying on the default `count_ones`
+    #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+    struct Custom(u16);
+
+    macro_rules! impl_ops {
+        ($($tr:ident $f:ident),*) => {
+            $(
+                impl ops::$tr for Custom {
+                    type Output = Self;
+
+                    fn $f(self, rhs: Self) -> Self {
+                        Custom(ops::$tr::$f(self.0, rhs.0))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_ops!(BitOr bitor, BitAnd bitand, BitXor bitxor, Shl shl, Shr shr, Add add);
+
+    macro_rules! impl_fmt {
+        ($($tr:ident),*) => {
+            $(
+                impl fmt::$tr for Custom {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        fmt::$tr::fmt(&self.0, f)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_fmt!(Display, Binary, LowerHex, UpperHex, Octal);
+
+    impl ops::Not for Custom {
+        type Output = Self;
+
+        fn not(self) -> Self {
+            Custom(!self.0)
+        }
+    }
+
+    impl From<u8> for Custom {
+        fn from(v: u8) -> Self {
+            Custom(v.into())
+        }
+    }
+
+    impl BitflagAble for Custom {
+        fn trailing_zeros(self) -> u32 {
+            self.0.trailing_zeros()
+        }
+
+        fn leading_zeros(self) -> u32 {
+            self.0.leading_zeros()
+        }
+
+        fn reverse_bits(self) -> Self {
+            Custom(self.0.reverse_bits())
+        }
+
+        fn rotate_left(self, n: u32) -> Self {
+            Custom(self.0.rotate_left(n))
+        }
+
+        fn rotate_right(self, n: u32) -> Self {
+            Custom(self.0.rotate_right(n))
+        }
+
+        fn swap_bytes(self) -> Self {
+            Custom(self.0.swap_bytes())
+        }
+
+        fn to_be(self) -> Self {
+            Custom(self.0.to_be())
+        }
+
+        fn to_le(self) -> Self {
+            Custom(self.0.to_le())
+        }
+
+        fn low_byte(self) -> u8 {
+            self.0 as u8
+        }
+
+        fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+            u16::from_str_radix(src, radix).map(Custom)
+        }
+
+        fn to_usize(self) -> usize {
+            self.0 as usize
+        }
+
+        fn from_usize(pos: usize) -> Self {
+            Custom(pos as u16)
+        }
+
+        fn checked_add(self, rhs: Self) -> Option<Self> {
+            self.0.checked_add(rhs.0).map(Custom)
+        }
+
+        fn wrapping_add(self, rhs: Self) -> Self {
+            Custom(self.0.wrapping_add(rhs.0))
+        }
+
+        fn saturating_add(self, rhs: Self) -> Self {
+            Custom(self.0.saturating_add(rhs.0))
+        }
+    }
+
+    #[test]
+    fn test_default_count_ones() {
+        for v in [0u16, 1, 0b1011, 0x8000, 0xF0F0, u16::MAX] {
+            assert_eq!(BitflagAble::count_ones(Custom(v)), v.count_ones());
+        }
+
+        let mut bf: BitFlag<Custom> = BitFlag::new();
+        bf.set_at(3, true);
+        bf.set_at(15, true);
+        assert_eq!(bf.len(), 2);
+        assert_eq!(BitFlag::<Custom>::all().len(), 16);
+    }
 }
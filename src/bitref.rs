@@ -0,0 +1,118 @@
+use crate::{bitflagable::BitflagAble, BitFlag};
+
+/// Mutable handle to a single bit of a bitflag, created by [`BitFlag::at`].
+///
+/// All writes go directly through the borrowed bitflag. The position is checked once while
+/// creating the handle, so none of its methods can overflow.
+#[derive(Debug)]
+pub struct BitRef<'a, T: BitflagAble> {
+    flag: &'a mut BitFlag<T>,
+    pos: T,
+}
+
+impl<'a, T: BitflagAble> BitRef<'a, T> {
+    /// Creates a new handle to the bit at `pos`. `pos` must not overflow
+    #[inline]
+    pub(crate) fn new(flag: &'a mut BitFlag<T>, pos: T) -> Self {
+        Self { flag, pos }
+    }
+
+    /// Returns the position of the bit
+    #[inline]
+    pub fn pos(&self) -> T {
+        self.pos
+    }
+
+    /// Returns the current value of the bit
+    #[inline]
+    pub fn get(&self) -> bool {
+        self.flag.get_unchecked(self.pos)
+    }
+
+    /// Sets the bit to `val`
+    #[inline]
+    pub fn set(&mut self, val: bool) {
+        self.flag.set_unchecked(self.pos, val);
+    }
+
+    /// Toggles the bit
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.flag.toggle_unchecked(self.pos);
+    }
+
+    /// Sets the bit to `val` and returns its previous value
+    #[inline]
+    pub fn replace(&mut self, val: bool) -> bool {
+        self.flag.replace_unchecked(self.pos, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_set() {
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b100);
+
+        let mut bit = bf.at(2).unwrap();
+        assert_eq!(bit.pos(), 2);
+        assert!(bit.get());
+        bit.set(false);
+        assert!(!bit.get());
+        bit.set(true);
+        bit.set(true);
+        assert!(bit.get());
+
+        bf.at(7).unwrap().set(true);
+        assert_eq!(bf, 0b1000_0100u8);
+    }
+
+    #[test]
+    fn test_toggle_replace() {
+        let mut bf: BitFlag<u64> = BitFlag::new();
+
+        bf.at(5).unwrap().toggle();
+        assert_eq!(bf, 1u64 << 5);
+        bf.at(5).unwrap().toggle();
+        assert!(bf.is_empty());
+
+        let mut bit = bf.at(63).unwrap();
+        assert!(!bit.replace(true));
+        assert!(bit.replace(false));
+        assert!(!bit.replace(true));
+        assert_eq!(bf, 1u64 << 63);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        assert!(bf.at(8).is_none());
+        assert!(bf.at(200).is_none());
+
+        let mut bf: BitFlag<u128> = BitFlag::new();
+        assert!(bf.at(127).is_some());
+        assert!(bf.at(128).is_none());
+    }
+
+    #[test]
+    fn test_pass_to_helper() {
+        fn enable(mut bit: BitRef<'_, u16>) {
+            bit.set(true);
+        }
+
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        enable(bf.at(3).unwrap());
+        enable(bf.at(9).unwrap());
+        assert_eq!(bf, 0b10_0000_1000u16);
+
+        // The handle borrows the flag mutably, so it has to go out of scope before the flag
+        // can be used again
+        {
+            let mut bit = bf.at(3).unwrap();
+            bit.toggle();
+        }
+        assert_eq!(bf, 0b10_0000_0000u16);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{bitflagable::BitflagAble, error::BitFlagError, BitFlag};
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Write},
+};
+
+/// Byte order used to read and write bitflags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Least significant byte first
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+impl<T: BitflagAble> BitFlag<T> {
+    /// Writes the bitflag to `w` using the given byte order
+    #[inline]
+    pub fn write_to<W: Write>(&self, w: &mut W, endianness: Endianness) -> io::Result<()> {
+        let mut buf = [0u8; 16];
+        let buf = &mut buf[..Self::size() / 8];
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = self.get_byte(Self::byte_index(i, endianness)).unwrap_or(0);
+        }
+
+        w.write_all(buf)
+    }
+
+    /// Reads a bitflag from `r` using the given byte order. Fails with
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) if `r` ends too early
+    #[inline]
+    pub fn read_from<R: Read>(r: &mut R, endianness: Endianness) -> io::Result<Self> {
+        let mut buf = [0u8; 16];
+        let buf = &mut buf[..Self::size() / 8];
+        r.read_exact(buf)?;
+
+        Ok(Self::from_byte_slice(buf, endianness))
+    }
+
+    /// Creates a bitflag from `bytes` in big endian byte order. Fails if `bytes` isn't exactly as
+    /// long as T
+    #[inline]
+    pub fn from_be_slice(bytes: &[u8]) -> Result<Self, BitFlagError> {
+        Self::check_len(bytes)?;
+        Ok(Self::from_byte_slice(bytes, Endianness::Big))
+    }
+
+    /// Returns an error if `bytes` isn't exactly as long as T
+    #[inline]
+    fn check_len(bytes: &[u8]) -> Result<(), BitFlagError> {
+        if bytes.len() != Self::size() / 8 {
+            return Err(BitFlagError::InvalidLength {
+                expected: Self::size() / 8,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Builds a bitflag from `bytes` in the given byte order. `bytes` must be exactly as long as T
+    #[inline]
+    fn from_byte_slice(bytes: &[u8], endianness: Endianness) -> Self {
+        let mut bf = Self::new();
+        for (i, byte) in bytes.iter().enumerate() {
+            bf.set_byte(Self::byte_index(i, endianness), *byte);
+        }
+        bf
+    }
+
+    /// Maps the index of a byte in a serialized bitflag to the index used by
+    /// [`get_byte`](Self::get_byte)
+    #[inline]
+    fn byte_index(i: usize, endianness: Endianness) -> usize {
+        match endianness {
+            Endianness::Little => i,
+            Endianness::Big => Self::size() / 8 - 1 - i,
+        }
+    }
+}
+
+macro_rules! impl_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl BitFlag<$t> {
+                /// Returns the memory representation of the bitflag in little endian byte order
+                #[inline]
+                pub fn to_le_bytes(&self) -> [u8; std::mem::size_of::<$t>()] {
+                    self.raw().to_le_bytes()
+                }
+
+                /// Returns the memory representation of the bitflag in big endian byte order
+                #[inline]
+                pub fn to_be_bytes(&self) -> [u8; std::mem::size_of::<$t>()] {
+                    self.raw().to_be_bytes()
+                }
+
+                /// Creates a bitflag from its memory representation in little endian byte order
+                #[inline]
+                pub fn from_le_bytes(bytes: [u8; std::mem::size_of::<$t>()]) -> Self {
+                    Self::new_with_value(<$t>::from_le_bytes(bytes))
+                }
+
+                /// Creates a bitflag from its memory representation in big endian byte order
+                #[inline]
+                pub fn from_be_bytes(bytes: [u8; std::mem::size_of::<$t>()]) -> Self {
+                    Self::new_with_value(<$t>::from_be_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+/// Creates a bitflag from `bytes` in little endian byte order. Fails if `bytes` isn't exactly as
+/// long as T, longer slices are not read partially. Use [`BitFlag::from_be_slice`] for big endian
+/// input.
+impl<T: BitflagAble> TryFrom<&[u8]> for BitFlag<T> {
+    type Error = BitFlagError;
+
+    #[inline]
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::check_len(bytes)?;
+        Ok(Self::from_byte_slice(bytes, Endianness::Little))
+    }
+}
+
+impl_bytes!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_round_trip() {
+        macro_rules! check {
+            ($($t:ty),*) => {
+                $(
+                    let bf: BitFlag<$t> = BitFlag::new_with_value(<$t>::MAX / 3 - 7);
+                    assert_eq!(BitFlag::<$t>::from_le_bytes(bf.to_le_bytes()), bf);
+                    assert_eq!(BitFlag::<$t>::from_be_bytes(bf.to_be_bytes()), bf);
+                    assert_eq!(bf.to_le_bytes().len(), BitFlag::<$t>::size() / 8);
+                )*
+            };
+        }
+
+        check!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+    }
+
+    #[test]
+    fn test_byte_order() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0x1234_5678);
+        assert_eq!(bf.to_le_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(bf.to_be_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        assert_ne!(bf.to_le_bytes(), bf.to_be_bytes());
+
+        // Byte 0 of the little endian representation holds bits 0 to 7
+        let bf: BitFlag<u16> = BitFlag::bit(0);
+        assert_eq!(bf.to_le_bytes(), [1, 0]);
+        assert_eq!(bf.to_be_bytes(), [0, 1]);
+        assert_eq!(BitFlag::<u16>::from_be_bytes([0, 1]), bf);
+        assert_eq!(BitFlag::<u16>::from_le_bytes([0, 1]), BitFlag::bit(8));
+
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0xA5);
+        assert_eq!(bf.to_le_bytes(), bf.to_be_bytes());
+    }
+
+    #[test]
+    fn test_write_read() {
+        use std::io::Cursor;
+
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0x1234_5678);
+        let mut cursor = Cursor::new(Vec::new());
+        bf.write_to(&mut cursor, Endianness::Little).unwrap();
+        bf.write_to(&mut cursor, Endianness::Big).unwrap();
+        assert_eq!(
+            cursor.get_ref(),
+            &vec![0x78, 0x56, 0x34, 0x12, 0x12, 0x34, 0x56, 0x78]
+        );
+
+        cursor.set_position(0);
+        let le = BitFlag::<u32>::read_from(&mut cursor, Endianness::Little).unwrap();
+        let be = BitFlag::<u32>::read_from(&mut cursor, Endianness::Big).unwrap();
+        assert_eq!(le, bf);
+        assert_eq!(be, bf);
+
+        let bf: BitFlag<u128> = BitFlag::new_with_value(u128::MAX / 7);
+        let mut cursor = Cursor::new(Vec::new());
+        bf.write_to(&mut cursor, Endianness::Big).unwrap();
+        assert_eq!(cursor.get_ref(), &bf.to_be_bytes().to_vec());
+        cursor.set_position(0);
+        assert_eq!(
+            BitFlag::read_from(&mut cursor, Endianness::Big).unwrap(),
+            bf
+        );
+
+        let bf: BitFlag<i16> = BitFlag::new_with_value(-2);
+        let mut cursor = Cursor::new(Vec::new());
+        bf.write_to(&mut cursor, Endianness::Little).unwrap();
+        cursor.set_position(0);
+        assert_eq!(
+            BitFlag::read_from(&mut cursor, Endianness::Little).unwrap(),
+            bf
+        );
+    }
+
+    #[test]
+    fn test_read_truncated() {
+        use std::io::{Cursor, ErrorKind};
+
+        let mut cursor = Cursor::new(vec![1u8, 2, 3]);
+        let err = BitFlag::<u32>::read_from(&mut cursor, Endianness::Little).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        let mut cursor = Cursor::new(Vec::new());
+        let err = BitFlag::<u8>::read_from(&mut cursor, Endianness::Big).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let buf = [0x78u8, 0x56, 0x34, 0x12, 0xFF, 0xFF];
+
+        let bf = BitFlag::<u32>::try_from(&buf[..4]).unwrap();
+        assert_eq!(bf, 0x1234_5678u32);
+        assert_eq!(
+            BitFlag::<u32>::from_be_slice(&buf[..4]),
+            Ok(BitFlag::new_with_value(0x7856_3412))
+        );
+        assert_eq!(BitFlag::<u16>::try_from(&buf[4..]), Ok(BitFlag::all()));
+
+        // Short slices
+        assert_eq!(
+            BitFlag::<u32>::try_from(&buf[..3]),
+            Err(BitFlagError::InvalidLength {
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert!(BitFlag::<u8>::try_from(&buf[..0]).is_err());
+
+        // Long slices
+        assert_eq!(
+            BitFlag::<u32>::try_from(&buf[..]),
+            Err(BitFlagError::InvalidLength {
+                expected: 4,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            BitFlag::<u64>::from_be_slice(&buf[..]),
+            Err(BitFlagError::InvalidLength {
+                expected: 8,
+                actual: 6
+            })
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use crate::{error::BitFlagError, BitFlag};
+use std::convert::TryFrom;
+
+macro_rules! impl_widening {
+    ($($src:ty as $unsigned:ty => $($dst:ty),*);* $(;)?) => {
+        $($(
+            /// Converts to a wider bitflag, keeping all bits at their position. Signed types are
+            /// not sign extended.
+            impl From<BitFlag<$src>> for BitFlag<$dst> {
+                #[inline]
+                fn from(bf: BitFlag<$src>) -> Self {
+                    BitFlag::new_with_value(bf.raw() as $unsigned as $dst)
+                }
+            }
+        )*)*
+    };
+}
+
+macro_rules! impl_narrowing {
+    ($($src:ty as $unsigned:ty => $($dst:ty),*);* $(;)?) => {
+        $($(
+            /// Converts to a bitflag that might have fewer bits, keeping all bits at their
+            /// position. Fails with the position of the highest bit that doesn't fit.
+            impl TryFrom<BitFlag<$src>> for BitFlag<$dst> {
+                type Error = BitFlagError;
+
+                #[inline]
+                fn try_from(bf: BitFlag<$src>) -> Result<Self, Self::Error> {
+                    match bf.last_set() {
+                        Some(pos) if pos >= Self::size() => Err(BitFlagError::PositionOutOfRange {
+                            pos,
+                            width: Self::size(),
+                        }),
+                        _ => Ok(BitFlag::new_with_value(bf.raw() as $unsigned as $dst)),
+                    }
+                }
+            }
+        )*)*
+    };
+}
+
+// Every source converts to all fixed width types with more bits, regardless of their signedness.
+// `usize` and `isize` have at least 16 bits, so all sources with up to 16 bits fit into both.
+impl_widening!(
+    u8 as u8 => u16, u32, u64, u128, usize, i16, i32, i64, i128, isize;
+    u16 as u16 => u32, u64, u128, usize, i32, i64, i128, isize;
+    u32 as u32 => u64, u128, i64, i128;
+    u64 as u64 => u128, i128;
+    i16 as u16 => u32, u64, u128, usize, i32, i64, i128, isize;
+    i32 as u32 => u64, u128, i64, i128;
+    i64 as u64 => u128, i128;
+);
+
+// Every source converts fallibly to all types with fewer bits. The width of `usize` and `isize`
+// depends on the target, so they also convert fallibly to and from all fixed width types with
+// more than 16 bits.
+impl_narrowing!(
+    u16 as u16 => u8;
+    u32 as u32 => u8, u16, i16, usize, isize;
+    u64 as u64 => u8, u16, u32, i16, i32, usize, isize;
+    u128 as u128 => u8, u16, u32, u64, i16, i32, i64, usize, isize;
+    usize as usize => u8, u16, u32, u64, u128, i16, i32, i64, i128;
+    i16 as u16 => u8;
+    i32 as u32 => u8, u16, i16, usize, isize;
+    i64 as u64 => u8, u16, u32, i16, i32, usize, isize;
+    i128 as u128 => u8, u16, u32, u64, i16, i32, i64, usize, isize;
+    isize as usize => u8, u16, u32, u64, u128, i16, i32, i64, i128;
+);
+
+macro_rules! impl_halves {
+    ($($t:ty => $half:ty),*) => {
+        $(
+            impl BitFlag<$t> {
+                /// Splits the bitflag into its low and high half, in this order
+                #[inline]
+                pub fn split_halves(&self) -> (BitFlag<$half>, BitFlag<$half>) {
+                    let low = self.raw() as $half;
+                    let high = (self.raw() >> <$half>::BITS) as $half;
+                    (BitFlag::new_with_value(low), BitFlag::new_with_value(high))
+                }
+
+                /// Joins the `low` and `high` half into a single bitflag. Inverse of
+                /// [`split_halves`](Self::split_halves)
+                #[inline]
+                pub fn from_halves(low: BitFlag<$half>, high: BitFlag<$half>) -> Self {
+                    Self::concat(low, high)
+                }
+
+                /// Joins two bitflags into one of double width. The bits of `low` end up at the
+                /// bottom and the bits of `high` at the top of the result
+                #[inline]
+                pub fn concat(low: BitFlag<$half>, high: BitFlag<$half>) -> Self {
+                    BitFlag::new_with_value((high.raw() as $t) << <$half>::BITS | low.raw() as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_halves!(u16 => u8, u32 => u16, u64 => u32, u128 => u64);
+
+macro_rules! impl_bool_array {
+    ($($t:ty),*) => {
+        $(
+            impl BitFlag<$t> {
+                /// Returns the bits as array, where index 0 holds the least significant bit
+                #[inline]
+                pub fn to_bool_array(&self) -> [bool; std::mem::size_of::<$t>() * 8] {
+                    let mut bits = [false; std::mem::size_of::<$t>() * 8];
+                    for (bit, val) in bits.iter_mut().zip(self.iter()) {
+                        *bit = val;
+                    }
+                    bits
+                }
+
+                /// Creates a bitflag from an array of bits, where index 0 holds the least
+                /// significant bit
+                #[inline]
+                pub fn from_bool_array(bits: [bool; std::mem::size_of::<$t>() * 8]) -> Self {
+                    bits.iter().copied().collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_bool_array!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widen_keeps_positions() {
+        macro_rules! check {
+            ($src:ty => $($dst:ty),*) => {
+                $(
+                    let top = BitFlag::<$src>::size() - 1;
+                    let mut bf: BitFlag<$src> = BitFlag::new();
+                    bf.set_at(top, true);
+                    bf.set_at(0, true);
+
+                    let wide: BitFlag<$dst> = bf.into();
+                    assert_eq!(wide.iter_ones().collect::<Vec<_>>(), vec![0, top]);
+                    assert_eq!(BitFlag::<$dst>::from(bf).len(), 2);
+                )*
+            };
+        }
+
+        check!(u8 => u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+        check!(u16 => u32, u64, u128, usize, i32, i64, i128, isize);
+        check!(u32 => u64, u128, i64, i128);
+        check!(u64 => u128, i128);
+        check!(i16 => u32, u64, u128, usize, i32, i64, i128, isize);
+        check!(i32 => u64, u128, i64, i128);
+        check!(i64 => u128, i128);
+    }
+
+    #[test]
+    fn test_widen_section_mask() {
+        let section: BitFlag<u8> = BitFlag::new_with_value(0b1000_0001);
+        let mut aggregate: BitFlag<u64> = BitFlag::bit(40);
+        aggregate |= BitFlag::<u64>::from(section);
+        assert_eq!(aggregate, 1u64 << 40 | 0b1000_0001);
+
+        // No sign extension
+        let bf: BitFlag<i16> = BitFlag::bit(15);
+        assert_eq!(BitFlag::<i32>::from(bf), 1i32 << 15);
+    }
+
+    #[test]
+    fn test_narrow() {
+        let wide: BitFlag<u64> = BitFlag::new_with_value(0b1000_0001);
+        assert_eq!(
+            BitFlag::<u8>::try_from(wide),
+            Ok(BitFlag::new_with_value(0b1000_0001))
+        );
+        assert_eq!(
+            BitFlag::<u8>::try_from(BitFlag::<u128>::new()),
+            Ok(BitFlag::new())
+        );
+
+        let wide: BitFlag<u64> = BitFlag::new_with_value(1 << 63 | 1 << 9 | 1);
+        assert_eq!(
+            BitFlag::<u8>::try_from(wide),
+            Err(BitFlagError::PositionOutOfRange { pos: 63, width: 8 })
+        );
+        assert_eq!(
+            BitFlag::<u32>::try_from(wide),
+            Err(BitFlagError::PositionOutOfRange { pos: 63, width: 32 })
+        );
+
+        // Signed types narrow without looking at the sign
+        let bf: BitFlag<i32> = BitFlag::bit(15);
+        assert_eq!(BitFlag::<i16>::try_from(bf), Ok(BitFlag::bit(15)));
+        let bf: BitFlag<i64> = BitFlag::new_with_value(-1);
+        assert!(BitFlag::<i32>::try_from(bf).is_err());
+    }
+
+    #[test]
+    fn test_narrow_round_trip() {
+        macro_rules! check {
+            ($src:ty => $($dst:ty),*) => {
+                $(
+                    let bf: BitFlag<$src> = BitFlag::all();
+                    let wide: BitFlag<$dst> = bf.into();
+                    assert_eq!(BitFlag::<$src>::try_from(wide), Ok(bf));
+                )*
+            };
+        }
+
+        check!(u8 => u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+        check!(u16 => u32, u64, u128, usize, i32, i64, i128, isize);
+        check!(u32 => u64, u128, i64, i128);
+        check!(u64 => u128, i128);
+        check!(i16 => u32, u64, u128, usize, i32, i64, i128, isize);
+        check!(i32 => u64, u128, i64, i128);
+        check!(i64 => u128, i128);
+    }
+
+    #[test]
+    fn test_narrow_keeps_positions() {
+        macro_rules! check {
+            ($src:ty => $($dst:ty),*) => {
+                $(
+                    let top = BitFlag::<$src>::size().min(BitFlag::<$dst>::size()) - 1;
+                    let mut bf: BitFlag<$src> = BitFlag::new();
+                    bf.set_at(top, true);
+                    bf.set_at(0, true);
+
+                    let narrow = BitFlag::<$dst>::try_from(bf).unwrap();
+                    assert_eq!(narrow.iter_ones().collect::<Vec<_>>(), vec![0, top]);
+                )*
+            };
+        }
+
+        check!(u16 => u8);
+        check!(u32 => u8, u16, i16, usize, isize);
+        check!(u64 => u8, u16, u32, i16, i32, usize, isize);
+        check!(u128 => u8, u16, u32, u64, i16, i32, i64, usize, isize);
+        check!(usize => u8, u16, u32, u64, u128, i16, i32, i64, i128);
+        check!(i16 => u8);
+        check!(i32 => u8, u16, i16, usize, isize);
+        check!(i64 => u8, u16, u32, i16, i32, usize, isize);
+        check!(i128 => u8, u16, u32, u64, i16, i32, i64, usize, isize);
+        check!(isize => u8, u16, u32, u64, u128, i16, i32, i64, i128);
+    }
+
+    #[test]
+    fn test_narrow_pointer_sized() {
+        let ptr_width = BitFlag::<usize>::size();
+
+        let bf: BitFlag<u128> = BitFlag::bit(127);
+        assert_eq!(
+            BitFlag::<usize>::try_from(bf),
+            Err(BitFlagError::PositionOutOfRange {
+                pos: 127,
+                width: ptr_width
+            })
+        );
+        assert_eq!(
+            BitFlag::<isize>::try_from(bf),
+            Err(BitFlagError::PositionOutOfRange {
+                pos: 127,
+                width: ptr_width
+            })
+        );
+
+        // Sign bits aren't extended when converting to wider types
+        let bf: BitFlag<isize> = BitFlag::new_with_value(-1);
+        assert_eq!(BitFlag::<u128>::try_from(bf).unwrap().len(), ptr_width);
+        assert_eq!(BitFlag::<i128>::try_from(bf).unwrap().len(), ptr_width);
+
+        let bf: BitFlag<usize> = BitFlag::all();
+        if ptr_width > 32 {
+            assert_eq!(
+                BitFlag::<u32>::try_from(bf),
+                Err(BitFlagError::PositionOutOfRange {
+                    pos: ptr_width - 1,
+                    width: 32
+                })
+            );
+            assert!(BitFlag::<i32>::try_from(BitFlag::<isize>::all()).is_err());
+            assert_eq!(
+                BitFlag::<usize>::try_from(BitFlag::<u64>::all()),
+                Ok(BitFlag::all())
+            );
+        } else {
+            assert_eq!(BitFlag::<u32>::try_from(bf), Ok(BitFlag::all()));
+            assert_eq!(
+                BitFlag::<usize>::try_from(BitFlag::<u64>::all()),
+                Err(BitFlagError::PositionOutOfRange {
+                    pos: 63,
+                    width: ptr_width
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncate() {
+        let wide: BitFlag<u64> = BitFlag::new_with_value(1 << 63 | 1 << 9 | 1 << 7 | 1);
+        assert_eq!(wide.truncate::<u8>(), 0b1000_0001u8);
+        assert_eq!(wide.truncate::<u16>(), 0b10_1000_0001u16);
+        assert_eq!(wide.truncate::<u64>(), wide);
+
+        let bf: BitFlag<u128> = BitFlag::all();
+        assert!(bf.truncate::<i16>().is_full());
+    }
+
+    #[test]
+    fn test_split_halves() {
+        let bf: BitFlag<u16> = BitFlag::new_with_value(0xAB_CD);
+        let (low, high) = bf.split_halves();
+        assert_eq!(low, 0xCDu8);
+        assert_eq!(high, 0xABu8);
+
+        let bf: BitFlag<u128> = BitFlag::bit(127);
+        let (low, high) = bf.split_halves();
+        assert!(low.is_empty());
+        assert_eq!(high, 1u64 << 63);
+
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0x0001_8000);
+        assert_eq!(bf.split_halves(), (BitFlag::bit(15), BitFlag::bit(0)));
+    }
+
+    #[test]
+    fn test_halves_round_trip() {
+        macro_rules! check {
+            ($($t:ty => $half:ty),*) => {
+                $(
+                    let bf: BitFlag<$t> = BitFlag::new_with_value(<$t>::MAX / 3 * 2);
+                    let (low, high) = bf.split_halves();
+                    assert_eq!(BitFlag::<$t>::from_halves(low, high), bf);
+
+                    let low: BitFlag<$half> = BitFlag::bit(0);
+                    let high: BitFlag<$half> = BitFlag::bit(0);
+                    let joined = BitFlag::<$t>::from_halves(low, high);
+                    assert_eq!(joined.iter_ones().collect::<Vec<_>>(), vec![0, <$half>::BITS as usize]);
+                )*
+            };
+        }
+
+        check!(u16 => u8, u32 => u16, u64 => u32, u128 => u64);
+    }
+
+    #[test]
+    fn test_concat() {
+        let low: BitFlag<u32> = BitFlag::new_with_value(0xCAFE_BABE);
+        let high: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+        let joined = BitFlag::<u64>::concat(low, high);
+        assert_eq!(joined, 0xDEAD_BEEF_CAFE_BABEu64);
+        assert_eq!(joined.split_halves(), (low, high));
+
+        // The order matters
+        assert_eq!(
+            BitFlag::<u16>::concat(BitFlag::bit(0), BitFlag::new()),
+            1u16
+        );
+        assert_eq!(
+            BitFlag::<u16>::concat(BitFlag::new(), BitFlag::bit(0)),
+            1u16 << 8
+        );
+
+        let joined = BitFlag::<u128>::concat(BitFlag::all(), BitFlag::new());
+        assert_eq!(joined, u64::MAX as u128);
+        assert_eq!(
+            BitFlag::<u32>::concat(BitFlag::bit(15), BitFlag::bit(15)),
+            0x8000_8000u32
+        );
+    }
+
+    #[test]
+    fn test_bool_array() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1000_0011);
+        let bits = bf.to_bool_array();
+        assert_eq!(bits, [true, true, false, false, false, false, false, true]);
+        assert_eq!(BitFlag::<u8>::from_bool_array(bits), bf);
+
+        // Index 0 is bit position 0
+        let mut bits = [false; 64];
+        bits[0] = true;
+        assert_eq!(BitFlag::<u64>::from_bool_array(bits), 1u64);
+        assert!(BitFlag::<u64>::bit(0).to_bool_array()[0]);
+        assert!(BitFlag::<u64>::bit(63).to_bool_array()[63]);
+    }
+
+    #[test]
+    fn test_bool_array_round_trip() {
+        macro_rules! check {
+            ($($t:ty),*) => {
+                $(
+                    let bf: BitFlag<$t> = BitFlag::new_with_value(<$t>::MAX / 5 * 3);
+                    let bits = bf.to_bool_array();
+                    assert_eq!(bits.len(), BitFlag::<$t>::size());
+                    assert_eq!(bits.iter().filter(|b| **b).count(), bf.len());
+                    assert_eq!(BitFlag::<$t>::from_bool_array(bits), bf);
+                )*
+            };
+        }
+
+        check!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+/// Error returned by the fallible bitflag operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitFlagError {
+    /// The position `pos` doesn't fit into a bitflag with `width` bits
+    PositionOutOfRange { pos: usize, width: usize },
+    /// The range starts after it ends
+    InvalidRange { start: usize, end: usize },
+    /// The value needs `required_bits` bits but only `available` bits are available
+    ValueTooWide {
+        required_bits: usize,
+        available: usize,
+    },
+    /// The input has `actual` bytes instead of the `expected` amount
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl Display for BitFlagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitFlagError::PositionOutOfRange { pos, width } => write!(
+                f,
+                "position {} is out of range for a bitflag of {} bits",
+                pos, width
+            ),
+            BitFlagError::InvalidRange { start, end } => {
+                write!(f, "invalid range ({}, {}): start is after end", start, end)
+            }
+            BitFlagError::ValueTooWide {
+                required_bits,
+                available,
+            } => write!(
+                f,
+                "value needs {} bits but only {} bits are available",
+                required_bits, available
+            ),
+            BitFlagError::InvalidLength { expected, actual } => {
+                write!(f, "expected {} bytes but got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl Error for BitFlagError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = BitFlagError::PositionOutOfRange { pos: 8, width: 8 };
+        assert_eq!(
+            err.to_string(),
+            "position 8 is out of range for a bitflag of 8 bits"
+        );
+
+        let err = BitFlagError::InvalidRange { start: 5, end: 2 };
+        assert_eq!(err.to_string(), "invalid range (5, 2): start is after end");
+
+        let err = BitFlagError::ValueTooWide {
+            required_bits: 5,
+            available: 4,
+        };
+        assert_eq!(
+            err.to_string(),
+            "value needs 5 bits but only 4 bits are available"
+        );
+
+        let err = BitFlagError::InvalidLength {
+            expected: 8,
+            actual: 3,
+        };
+        assert_eq!(err.to_string(), "expected 8 bytes but got 3");
+    }
+
+    #[test]
+    fn test_error_trait() {
+        let err: Box<dyn Error> = Box::new(BitFlagError::InvalidRange { start: 1, end: 0 });
+        assert!(err.source().is_none());
+        assert_eq!(err.to_string(), "invalid range (1, 0): start is after end");
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{bitflagable::BitflagAble, BitFlag};
+use std::iter::{FromIterator, FusedIterator, Sum};
+
+/// Iterator over all fields of a bitflag, starting with the least significant bit.
+///
+/// The iterator works on a copy of the bitflag, so it doesn't borrow the flag it was created from.
+#[derive(Clone, Copy, Debug)]
+pub struct BitIter<T: BitflagAble> {
+    flag: BitFlag<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<T: BitflagAble> BitIter<T> {
+    /// Creates a new iterator over all fields of `flag`
+    #[inline]
+    pub(crate) fn new(flag: BitFlag<T>) -> Self {
+        Self {
+            flag,
+            front: 0,
+            back: BitFlag::<T>::size(),
+        }
+    }
+}
+
+impl<T: BitflagAble> Iterator for BitIter<T> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let bit = self.flag.get_unchecked(T::from(self.front as u8));
+        self.front += 1;
+        Some(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T: BitflagAble> DoubleEndedIterator for BitIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.flag.get_unchecked(T::from(self.back as u8)))
+    }
+}
+
+impl<T: BitflagAble> ExactSizeIterator for BitIter<T> {}
+
+impl<T: BitflagAble> FusedIterator for BitIter<T> {}
+
+impl<T: BitflagAble> IntoIterator for BitFlag<T> {
+    type Item = bool;
+    type IntoIter = BitIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        BitIter::new(self)
+    }
+}
+
+impl<T: BitflagAble> IntoIterator for &BitFlag<T> {
+    type Item = bool;
+    type IntoIter = BitIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        BitIter::new(*self)
+    }
+}
+
+/// Builds a bitflag by assigning the items to the positions 0, 1, 2, ... Items exceeding
+/// [`BitFlag::size`] are ignored and remaining bits are left clear.
+impl<T: BitflagAble> FromIterator<bool> for BitFlag<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bf = BitFlag::new();
+
+        for (pos, bit) in iter.into_iter().take(Self::size()).enumerate() {
+            bf.set_unchecked(T::from(pos as u8), bit);
+        }
+
+        bf
+    }
+}
+
+/// Builds a bitflag by setting the bit at every position yielded by the iterator. Overflowing
+/// positions are ignored.
+impl<T: BitflagAble> FromIterator<T> for BitFlag<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut bf = BitFlag::new();
+        bf.extend(iter);
+        bf
+    }
+}
+
+/// Sets the bit at every position yielded by the iterator. Overflowing positions are ignored.
+///
+/// Extending is always based on positions. There is no `Extend<bool>` as it is unclear at which
+/// position the new bits should be placed.
+impl<T: BitflagAble> Extend<T> for BitFlag<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for pos in iter {
+            if !BitFlag::<T>::is_overflow(pos) {
+                self.set_unchecked(pos, true);
+            }
+        }
+    }
+}
+
+/// Builds the union of all bitflags.
+impl<T: BitflagAble> FromIterator<BitFlag<T>> for BitFlag<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = BitFlag<T>>>(iter: I) -> Self {
+        iter.into_iter().fold(BitFlag::new(), |acc, bf| acc | bf)
+    }
+}
+
+/// Sums up bitflags by building their union. Unlike the `Add` operator this doesn't carry
+/// over bits set in both operands.
+impl<T: BitflagAble> Sum<BitFlag<T>> for BitFlag<T> {
+    #[inline]
+    fn sum<I: Iterator<Item = BitFlag<T>>>(iter: I) -> Self {
+        iter.collect()
+    }
+}
+
+/// Sums up bitflags by building their union. Unlike the `Add` operator this doesn't carry
+/// over bits set in both operands.
+impl<'a, T: BitflagAble> Sum<&'a BitFlag<T>> for BitFlag<T> {
+    #[inline]
+    fn sum<I: Iterator<Item = &'a BitFlag<T>>>(iter: I) -> Self {
+        iter.copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1000_0011);
+        let bits: Vec<_> = bf.iter().collect();
+        assert_eq!(
+            bits,
+            vec![true, true, false, false, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1000_0011);
+        let bits: String = bf.iter().rev().map(|b| if b { '1' } else { '0' }).collect();
+        assert_eq!(bits, "10000011");
+    }
+
+    #[test]
+    fn test_iter_len() {
+        let bf: BitFlag<u128> = BitFlag::all();
+        let mut iter = bf.iter();
+        assert_eq!(iter.len(), 128);
+
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 126);
+
+        // Meet in the middle
+        let mut iter = BitFlag::<u8>::bit(4).iter();
+        assert_eq!(iter.nth(3), Some(false));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.next_back(), Some(true));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0b1011);
+
+        let mut set = 0;
+        for bit in &bf {
+            if bit {
+                set += 1;
+            }
+        }
+        assert_eq!(set, 3);
+
+        assert_eq!(bf.into_iter().count(), BitFlag::<u32>::size());
+
+        fn count_set<I: IntoIterator<Item = bool>>(bits: I) -> usize {
+            bits.into_iter().filter(|b| *b).count()
+        }
+        assert_eq!(count_set(bf), 3);
+    }
+
+    #[test]
+    fn test_from_iter_bool() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+        assert_eq!(bf.iter().collect::<BitFlag<u32>>(), bf);
+
+        // Shorter iterators leave the remaining bits clear
+        let bf: BitFlag<u8> = vec![true, false, true].into_iter().collect();
+        assert_eq!(bf.raw(), 0b101);
+
+        // Longer iterators are cut off
+        let bf: BitFlag<u8> = vec![true; 100].into_iter().collect();
+        assert!(bf.is_full());
+
+        let bf: BitFlag<u8> = std::iter::empty::<bool>().collect();
+        assert!(bf.is_empty());
+    }
+
+    #[test]
+    fn test_from_iter_positions() {
+        let ids: Vec<u64> = vec![1, 5, 63];
+        let bf = ids.iter().copied().collect::<BitFlag<u64>>();
+        assert_eq!(bf.raw(), 1 << 63 | 0b100010);
+
+        // Duplicates are idempotent and overflowing positions are ignored
+        let bf: BitFlag<u8> = vec![2, 2, 2, 8, 100].into_iter().collect();
+        assert_eq!(bf.raw(), 0b100);
+
+        let mut bf: BitFlag<u8> = BitFlag::bit(0);
+        bf.extend(vec![0, 7, 9]);
+        assert_eq!(bf.raw(), 0b1000_0001);
+    }
+
+    #[test]
+    fn test_union_sum() {
+        let masks: Vec<BitFlag<u64>> = vec![
+            BitFlag::new_with_value(0b0011),
+            BitFlag::new_with_value(0b0110),
+            BitFlag::new_with_value(0b1100),
+        ];
+
+        let sum: BitFlag<u64> = masks.iter().sum();
+        assert_eq!(sum.raw(), 0b1111);
+        assert_eq!(sum.len(), 4);
+
+        let sum: BitFlag<u64> = masks.clone().into_iter().sum();
+        assert_eq!(sum.raw(), 0b1111);
+
+        let union: BitFlag<u64> = masks.iter().copied().collect();
+        assert_eq!(union.raw(), 0b1111);
+
+        // The Add operator carries instead
+        let added = masks.iter().fold(BitFlag::new(), |acc, bf| acc + *bf);
+        assert_eq!(added.raw(), 0b10101);
+
+        let empty: BitFlag<u64> = std::iter::empty::<BitFlag<u64>>().sum();
+        assert!(empty.is_empty());
+    }
+}
@@ -1,13 +1,36 @@
 pub mod bitflagable;
+pub mod bitref;
+pub mod bytes;
+mod convert;
+pub mod error;
+pub mod iter;
+pub mod parse;
+pub mod policy;
+#[cfg(feature = "with_serde")]
+pub mod serde_support;
 
 use bitflagable::BitflagAble;
+use bitref::BitRef;
+use error::BitFlagError;
+use iter::BitIter;
 use std::{
-    fmt::{Debug, Display},
-    ops::{Add, AddAssign},
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::{Binary, Debug, Display, LowerHex, Octal, UpperHex},
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound,
+        Index, Not, RangeBounds, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+    },
 };
 
 /// Wrapper for any type T that can be used for bitflags.
-#[derive(Clone, Copy, Default)]
+///
+/// BitFlags are ordered by their raw value, not by the subset relation.
+///
+/// `BitFlag<T>` is guaranteed to have the same layout as T, so it can be passed over FFI or
+/// casted from and to T.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
 pub struct BitFlag<T> {
     val: T,
 }
@@ -25,17 +48,120 @@ impl<T: BitflagAble> BitFlag<T> {
         Self { val }
     }
 
+    /// Creates a new BitFlag with all bits set
+    #[inline]
+    pub fn all() -> Self {
+        Self::new_with_value(Self::invert(T::from(0u8)))
+    }
+
+    /// Creates a new BitFlag with only the bit at `pos` set. Returns an empty BitFlag if `pos`
+    /// would cause an overflow
+    #[inline]
+    pub fn bit(pos: T) -> Self {
+        let mut bf = Self::new();
+        if !Self::is_overflow(pos) {
+            bf.set_unchecked(pos, true);
+        }
+        bf
+    }
+
+    /// Creates a new BitFlag with all bits from `start` to `end` (inclusive) set. Returns an
+    /// empty BitFlag if the range is invalid
+    #[inline]
+    pub fn mask(range: (u8, u8)) -> Self {
+        Self::mask_at(Self::widen(range))
+    }
+
+    /// Same as [`mask`](Self::mask) but takes the range as plain indices
+    #[inline]
+    pub fn mask_at(range: (usize, usize)) -> Self {
+        if !Self::is_valid_range(range) {
+            return Self::new();
+        }
+
+        Self::new_with_value(Self::span_mask(range))
+    }
+
+    /// Creates a new BitFlag with all bits below `n` set. Returns a full BitFlag if `n` is
+    /// greater or equal to [`size`](Self::size)
+    #[inline]
+    pub fn low_mask(n: T) -> Self {
+        if Self::is_overflow(n) {
+            return Self::all();
+        }
+
+        Self::new_with_value(Self::mask_below(n))
+    }
+
+    /// Creates a new BitFlag where each bit at the position `i` is set to `f(i)`
+    #[inline]
+    pub fn from_fn<F: FnMut(usize) -> bool>(mut f: F) -> Self {
+        let mut bf = Self::new();
+
+        for i in 0..Self::size() {
+            bf.set_unchecked(T::from(i as u8), f(i));
+        }
+
+        bf
+    }
+
+    /// Returns the intersection of all bitflags. Stops consuming the iterator as soon as the
+    /// intersection is empty. Returns a full BitFlag if the iterator is empty
+    #[inline]
+    pub fn intersect_all<I: IntoIterator<Item = BitFlag<T>>>(iter: I) -> Self {
+        let mut acc = Self::all();
+
+        for bf in iter {
+            acc &= bf;
+            if acc.is_empty() {
+                break;
+            }
+        }
+
+        acc
+    }
+
+    /// Returns the union of all bitflags. Returns an empty BitFlag if the iterator is empty
+    #[inline]
+    pub fn union_all<I: IntoIterator<Item = BitFlag<T>>>(iter: I) -> Self {
+        iter.into_iter().collect()
+    }
+
     /// Sets a bit at the given `pos` to `val`
     #[inline]
     pub fn set(&mut self, pos: T, val: bool) {
         // Check for overflow
         if Self::is_overflow(pos) {
+            #[cfg(feature = "strict")]
+            Self::strict_overflow(pos);
             return;
         }
 
         self.set_unchecked(pos, val);
     }
 
+    /// Gets a bit at the given `pos` or `None` if `pos` would cause an overflow
+    #[inline]
+    pub fn try_get(&self, pos: T) -> Option<bool> {
+        if Self::is_overflow(pos) {
+            return None;
+        }
+
+        Some(self.get_unchecked(pos))
+    }
+
+    /// Sets a bit at the given `pos` to `val`. Fails without changing anything if `pos` would
+    /// cause an overflow
+    #[inline]
+    pub fn try_set(&mut self, pos: T, val: bool) -> Result<(), BitFlagError> {
+        if Self::is_overflow(pos) {
+            return Err(Self::out_of_range(pos));
+        }
+
+        self.set_unchecked(pos, val);
+        Ok(())
+    }
+
     /// Sets a bit at the given `pos` to `val` without overflow checks
     #[inline]
     pub fn set_unchecked(&mut self, pos: T, val: bool) {
@@ -48,309 +174,3715 @@ impl<T: BitflagAble> BitFlag<T> {
         }
     }
 
-    /// Set the bitflags value from `start` to `end` (inclusive) to `val`[0..end-start+1]
+    /// Returns the bitflag with the bit at `pos` set to `val`. Follows the overflow rules of
+    /// [`BitFlag::set`]
     #[inline]
-    pub fn set_range<V: Into<BitFlag<T>>>(&mut self, range: (u8, u8), val: V) {
-        if range.0 > range.1 || Self::is_overflow(T::from(range.1)) {
-            return;
+    pub fn with_bit(mut self, pos: T, val: bool) -> Self {
+        self.set(pos, val);
+        self
+    }
+
+    /// Sets a bit at the given `pos` to `val` and returns its previous value. Returns `false`
+    /// without changing anything if `pos` would cause an overflow
+    #[inline]
+    pub fn replace(&mut self, pos: T, val: bool) -> bool {
+        if Self::is_overflow(pos) {
+            return false;
         }
 
-        self.set_range_unchecked(range, val);
+        self.replace_unchecked(pos, val)
     }
 
-    /// Set the bitflags value from `start` to `end` (inclusive) to `val`[0..end-start+1]
+    /// Sets a bit at the given `pos` to `val` and returns its previous value without overflow
+    /// checks
     #[inline]
-    pub fn set_range_unchecked<V: Into<BitFlag<T>>>(&mut self, range: (u8, u8), val: V) {
-        let val = val.into();
+    pub fn replace_unchecked(&mut self, pos: T, val: bool) -> bool {
+        let mask = T::from(1_u8) << pos;
+        let prev = (self.val & mask) != T::from(0u8);
 
-        for (i, flag_pos) in (range.0..=range.1).enumerate() {
-            self.set_unchecked(T::from(flag_pos), val.get_unchecked(T::from(i as u8)));
+        if val {
+            self.val = self.val | mask;
+        } else {
+            self.val = self.val & Self::invert(mask);
         }
+
+        prev
     }
 
-    /// Get the value between `start` and `end` as T
+    /// Sets the bit at the given `pos` and returns whether it was already set. Returns `false`
+    /// without changing anything if `pos` would cause an overflow
     #[inline]
-    pub fn get_range(&self, range: (u8, u8)) -> Option<T> {
-        if range.0 > range.1 || Self::is_overflow(T::from(range.1)) {
-            return None;
-        }
-
-        Some(self.get_range_unchecked(range))
+    pub fn test_and_set(&mut self, pos: T) -> bool {
+        self.replace(pos, true)
     }
 
-    /// Get the value between `start` and `end` as T unchecked
+    /// Clears the bit at the given `pos` and returns whether it was set. Returns `false`
+    /// without changing anything if `pos` would cause an overflow
     #[inline]
-    pub fn get_range_unchecked(&self, range: (u8, u8)) -> T {
-        let mut cpy: BitFlag<T> = BitFlag::new();
+    pub fn test_and_clear(&mut self, pos: T) -> bool {
+        self.replace(pos, false)
+    }
 
-        for (i, flag_pos) in (range.0..=range.1).enumerate() {
-            cpy.set_unchecked(T::from(i as u8), self.get_unchecked(T::from(flag_pos)));
+    /// Sets the bit at the given `pos` and returns `true` if it wasn't set before. Returns
+    /// `false` without changing anything if `pos` would cause an overflow
+    #[inline]
+    pub fn insert(&mut self, pos: T) -> bool {
+        if Self::is_overflow(pos) {
+            return false;
         }
 
-        cpy.val
+        !self.replace_unchecked(pos, true)
     }
 
-    /// Gets a bit at the given [`pos`]
+    /// Clears the bit at the given `pos` and returns `true` if it was set before. Returns
+    /// `false` without changing anything if `pos` would cause an overflow
     #[inline]
-    pub fn get(&self, pos: T) -> bool {
+    pub fn remove(&mut self, pos: T) -> bool {
+        self.replace(pos, false)
+    }
+
+    /// Returns `true` if the bit at the given `pos` is set. Returns `false` if `pos` would cause
+    /// an overflow
+    #[inline]
+    pub fn contains(&self, pos: T) -> bool {
+        !Self::is_overflow(pos) && self.get_unchecked(pos)
+    }
+
+    /// Sets the bit at the given `pos` to the result of `f` called with its current value and
+    /// returns the new value. Returns `false` without calling `f` if `pos` would cause an overflow
+    #[inline]
+    pub fn update<F: FnOnce(bool) -> bool>(&mut self, pos: T, f: F) -> bool {
         if Self::is_overflow(pos) {
             return false;
         }
 
-        self.get_unchecked(pos)
+        let val = f(self.get_unchecked(pos));
+        self.set_unchecked(pos, val);
+        val
     }
 
-    /// Gets a bit at the given [`pos`]
+    /// Swaps the bits at the positions `a` and `b`. Does nothing if any of the positions would
+    /// cause an overflow
     #[inline]
-    pub fn get_unchecked(&self, pos: T) -> bool {
-        let mask = T::from(1u8) << pos;
-        (self.val & mask) != T::from(0u8)
+    pub fn swap_bits(&mut self, a: T, b: T) {
+        if Self::is_overflow(a) || Self::is_overflow(b) {
+            return;
+        }
+
+        if self.get_unchecked(a) != self.get_unchecked(b) {
+            self.val = self.val ^ ((T::from(1u8) << a) | (T::from(1u8) << b));
+        }
     }
 
-    /// Get the raw value of the bitflag
+    /// Clears all bits set for whose position `f` returns `false`. `f` is only called for bits set
     #[inline]
-    pub fn raw(&self) -> T {
-        self.val
+    pub fn retain<F: FnMut(usize) -> bool>(&mut self, mut f: F) {
+        for pos in self.iter_ones() {
+            if !f(pos) {
+                self.set_unchecked(T::from(pos as u8), false);
+            }
+        }
     }
 
-    /// Clears the value to `T::default()`
+    /// Sets all bits at the given `positions`. Overflowing positions are ignored
     #[inline]
-    pub fn clear(&mut self) {
-        self.val = T::default();
+    pub fn set_positions(&mut self, positions: &[T]) {
+        self.val = self.val | Self::positions_mask(positions);
     }
 
-    /// Returns true if [`pos`] would cause an overflow
+    /// Clears all bits at the given `positions`. Overflowing positions are ignored
     #[inline]
-    pub fn is_overflow(pos: T) -> bool {
-        pos >= T::from(Self::size() as u8)
+    pub fn clear_positions(&mut self, positions: &[T]) {
+        self.val = self.val & Self::invert(Self::positions_mask(positions));
     }
 
-    /// Returns the amonut of bits set
+    /// Flips the bit at the given `pos`
     #[inline]
-    pub fn len(&self) -> usize {
-        (0..Self::size())
-            .filter(|i| self.get_unchecked(T::from(*i as u8)))
-            .count()
+    pub fn toggle(&mut self, pos: T) {
+        // Check for overflow
+        if Self::is_overflow(pos) {
+            return;
+        }
+
+        self.toggle_unchecked(pos);
     }
 
-    /// Returns `true` if there is no bit set.
+    /// Flips the bit at the given `pos` without overflow checks
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        T::from(0u8) == self.val
+    pub fn toggle_unchecked(&mut self, pos: T) {
+        self.val = self.val ^ (T::from(1_u8) << pos);
     }
 
-    /// Returns an iterator over all fields of the bitflag.
+    /// Sets a bit at the given `pos` to `val`. Same as [`set`](Self::set) but takes the position
+    /// as plain index
     #[inline]
-    pub fn iter<'a>(&'a self) -> impl Iterator<Item = bool> + 'a {
-        (0..Self::size()).map(move |i| self.get_unchecked(T::from(i as u8)))
+    pub fn set_at(&mut self, pos: usize, val: bool) {
+        if let Some(pos) = Self::position(pos) {
+            self.set_unchecked(pos, val);
+        }
     }
 
-    /// Returns the amonut of bits that can be accessed for the given base type T
+    /// Gets a bit at the given `pos`. Same as [`get`](Self::get) but takes the position as plain
+    /// index
     #[inline]
-    pub fn size() -> usize {
-        std::mem::size_of::<T>() * 8
+    pub fn get_at(&self, pos: usize) -> bool {
+        match Self::position(pos) {
+            Some(pos) => self.get_unchecked(pos),
+            None => false,
+        }
     }
 
-    ///  Inverts all bits in [`val`]
+    /// Flips the bit at the given `pos`. Same as [`toggle`](Self::toggle) but takes the position
+    /// as plain index
     #[inline]
-    fn invert(val: T) -> T {
-        (!val) as T
+    pub fn toggle_at(&mut self, pos: usize) {
+        if let Some(pos) = Self::position(pos) {
+            self.toggle_unchecked(pos);
+        }
     }
-}
 
-impl<T: BitflagAble> Debug for BitFlag<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self)
+    /// Set the bitflags value from `start` to `end` (inclusive) to `val`[0..end-start+1]
+    ///
+    /// `val` has to convert into `BitFlag<T>`, so integer literals are inferred as T. Use
+    /// [`try_set_range_from`](Self::try_set_range_from) to write values of other widths
+    #[inline]
+    pub fn set_range<V: Into<BitFlag<T>>>(&mut self, range: (u8, u8), val: V) {
+        self.set_range_at(Self::widen(range), val);
     }
-}
 
-impl<T: BitflagAble> Display for BitFlag<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:b}", self.val)
-    }
-}
+    /// Set the bitflags value from `start` to `end` (inclusive) to `val`[0..end-start+1]. Same as
+    /// [`set_range`](Self::set_range) but takes the range as plain indices
+    #[inline]
+    pub fn set_range_at<V: Into<BitFlag<T>>>(&mut self, range: (usize, usize), val: V) {
+        if !Self::is_valid_range(range) {
+            #[cfg(feature = "strict")]
+            Self::strict_invalid_range(range);
+            return;
+        }
 
-impl<T: BitflagAble> Add<Self> for BitFlag<T> {
-    type Output = Self;
+        self.write_span(range, val.into());
+    }
 
+    /// Set the bitflags value from `start` to `end` (inclusive) to `val`, clipping `end` to the
+    /// last bit. Returns the number of bits written, which is 0 for reversed ranges
     #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        Self::new_with_value(self.val.add(rhs.val))
+    pub fn set_range_clamped<V: Into<BitFlag<T>>>(&mut self, range: (u8, u8), val: V) -> usize {
+        self.set_range_clamped_at(Self::widen(range), val)
     }
-}
 
-impl<T: BitflagAble, U: BitflagAble + Add<T, Output = T>> Add<U> for BitFlag<T> {
-    type Output = Self;
+    /// Same as [`set_range_clamped`](Self::set_range_clamped) but takes the range as plain indices
+    #[inline]
+    pub fn set_range_clamped_at<V: Into<BitFlag<T>>>(
+        &mut self,
+        range: (usize, usize),
+        val: V,
+    ) -> usize {
+        match Self::clamp_range(range) {
+            Some(range) => {
+                self.write_span(range, val.into());
+                Self::range_len(range)
+            }
+            None => 0,
+        }
+    }
 
+    /// Set the bits in `range` to `val`. Accepts all std range types, so `0..8` and `..` both
+    /// cover all bits of a `BitFlag<u8>`. Does nothing for empty ranges, overflowing ranges are
+    /// handled like in [`set_range`](Self::set_range)
     #[inline]
-    fn add(self, rhs: U) -> Self::Output {
-        let v = rhs.add(self.val);
-        Self::new_with_value(v)
+    pub fn set_bits<R: RangeBounds<usize>, V: Into<BitFlag<T>>>(&mut self, range: R, val: V) {
+        if let Some(range) = Self::bounds_to_range(range) {
+            self.set_range_at(range, val);
+        }
     }
-}
 
-impl<T: BitflagAble, U: BitflagAble + Add<T, Output = T>> AddAssign<U> for BitFlag<T> {
+    /// Set the bitflags value from `start` to `end` (inclusive) to `val`. Fails without changing
+    /// anything if the range is invalid or `val` has bits set outside of the range's width
     #[inline]
-    fn add_assign(&mut self, rhs: U) {
-        self.val = rhs.add(self.val)
+    pub fn try_set_range<V: Into<BitFlag<T>>>(
+        &mut self,
+        range: (u8, u8),
+        val: V,
+    ) -> Result<(), BitFlagError> {
+        self.try_set_range_at(Self::widen(range), val)
     }
-}
 
-impl<T: BitflagAble> AddAssign<Self> for BitFlag<T> {
+    /// Same as [`try_set_range`](Self::try_set_range) but takes the range as plain indices
     #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        self.val = self.val + rhs.val;
+    pub fn try_set_range_at<V: Into<BitFlag<T>>>(
+        &mut self,
+        range: (usize, usize),
+        val: V,
+    ) -> Result<(), BitFlagError> {
+        Self::check_range(range)?;
+
+        let val = val.into();
+        let required_bits = Self::size() - val.leading_zeros();
+        let available = Self::range_len(range);
+        if required_bits > available {
+            return Err(BitFlagError::ValueTooWide {
+                required_bits,
+                available,
+            });
+        }
+
+        self.write_span(range, val);
+        Ok(())
     }
-}
 
-impl<T: BitflagAble> From<T> for BitFlag<T> {
+    /// Set the bitflags value from `start` to `end` (inclusive) to `val` of any width, so fields
+    /// can be written without casting them to T first. Fails like
+    /// [`try_set_range`](Self::try_set_range) without changing anything
+    ///
+    /// This is a separate method as accepting any width in [`set_range`](Self::set_range) would
+    /// infer integer literals as `i32`, breaking calls with literals that only fit into T
     #[inline]
-    fn from(t: T) -> Self {
-        Self { val: t }
+    pub fn try_set_range_from<V: BitflagAble>(
+        &mut self,
+        range: (u8, u8),
+        val: V,
+    ) -> Result<(), BitFlagError> {
+        self.try_set_range_from_at(Self::widen(range), val)
     }
-}
 
-#[cfg(feature = "with_serde")]
-impl<T> serde::Serialize for BitFlag<T>
-where
-    T: serde::Serialize,
-{
+    /// Same as [`try_set_range_from`](Self::try_set_range_from)
+    /// but takes the range as plain indices
     #[inline]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        self.val.serialize(serializer)
+    pub fn try_set_range_from_at<V: BitflagAble>(
+        &mut self,
+        range: (usize, usize),
+        val: V,
+    ) -> Result<(), BitFlagError> {
+        Self::check_range(range)?;
+
+        let val = BitFlag::new_with_value(val);
+        let required_bits = BitFlag::<V>::size() - val.leading_zeros();
+        let available = Self::range_len(range);
+        if required_bits > available {
+            return Err(BitFlagError::ValueTooWide {
+                required_bits,
+                available,
+            });
+        }
+
+        self.write_span(range, val.cast::<T>());
+        Ok(())
     }
-}
 
-#[cfg(feature = "with_serde")]
-impl<'a, T> serde::Deserialize<'a> for BitFlag<T>
-where
-    T: serde::Deserialize<'a>,
-{
+    /// Set the bitflags value from `start` to `end` (inclusive) to `val`[0..end-start+1]
     #[inline]
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'a>,
-    {
-        let val = T::deserialize(deserializer)?;
-        Ok(BitFlag { val })
+    pub fn set_range_unchecked<V: Into<BitFlag<T>>>(&mut self, range: (u8, u8), val: V) {
+        self.set_range_at_unchecked(Self::widen(range), val)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Same as [`set_range_unchecked`](Self::set_range_unchecked)
+    /// but takes the range as plain indices
+    #[inline]
+    pub fn set_range_at_unchecked<V: Into<BitFlag<T>>>(&mut self, range: (usize, usize), val: V) {
+        self.write_span(range, val.into());
+    }
 
-    #[test]
-    fn check_overflow() {
-        let mut bf8: BitFlag<u8> = BitFlag::new();
-        assert!(bf8.is_empty());
-        // Set won't do anything as we're overflowing on purpose
-        bf8.set(8, true);
-        assert_eq!(bf8.get(8), false);
+    /// Returns the bitflag with the range set to `val`. Follows the overflow rules of
+    /// [`BitFlag::set_range`]
+    #[inline]
+    pub fn with_range<V: Into<BitFlag<T>>>(self, range: (u8, u8), val: V) -> Self {
+        self.with_range_at(Self::widen(range), val)
     }
 
-    #[test]
-    fn set_all_false_u8() {
-        let mut bf8: BitFlag<u8> = BitFlag::new();
-        for i in 0..7 {
-            bf8.set(i, false);
-            assert_eq!(bf8.get(i), false);
-        }
-        assert!(bf8.is_empty());
+    /// Same as [`with_range`](Self::with_range) but takes the range as plain indices
+    #[inline]
+    pub fn with_range_at<V: Into<BitFlag<T>>>(mut self, range: (usize, usize), val: V) -> Self {
+        self.set_range_at(range, val);
+        self
     }
 
-    #[test]
-    fn set_all_true_u8() {
-        let mut bf8: BitFlag<u8> = BitFlag::new();
-        for i in 0..7 {
-            bf8.set(i, true);
-            assert_eq!(bf8.get(i), true);
-        }
-        assert!(!bf8.is_empty());
+    /// Sets all bits from `start` to `end` (inclusive) to `val`
+    #[inline]
+    pub fn fill_range(&mut self, range: (u8, u8), val: bool) {
+        self.fill_range_at(Self::widen(range), val);
     }
 
-    #[test]
-    fn toggle_all_u8() {
-        let mut bf8: BitFlag<u8> = BitFlag::new();
-        for i in 0..7 {
-            bf8.set(i, true);
-            assert_eq!(bf8.get(i), true);
+    /// Same as [`fill_range`](Self::fill_range) but takes the range as plain indices
+    #[inline]
+    pub fn fill_range_at(&mut self, range: (usize, usize), val: bool) {
+        if !Self::is_valid_range(range) {
+            return;
+        }
 
-            bf8.set(i, false);
-            assert_eq!(bf8.get(i), false);
+        if val {
+            self.val = self.val | Self::span_mask(range);
+        } else {
+            self.val = self.val & Self::invert(Self::span_mask(range));
         }
-        assert!(bf8.is_empty());
     }
 
-    #[test]
-    fn set_all_false_u32() {
-        let mut bf8: BitFlag<u32> = BitFlag::new();
-        for i in 0..31 {
-            bf8.set(i, false);
-            assert_eq!(bf8.get(i), false);
-        }
+    /// Clears all bits from `start` to `end` (inclusive)
+    #[inline]
+    pub fn clear_range(&mut self, range: (u8, u8)) {
+        self.clear_range_at(Self::widen(range));
     }
 
-    #[test]
-    fn set_all_true_u32() {
-        let mut bf8: BitFlag<u32> = BitFlag::new();
+    /// Same as [`clear_range`](Self::clear_range) but takes the range as plain indices
+    #[inline]
+    pub fn clear_range_at(&mut self, range: (usize, usize)) {
+        if !Self::is_valid_range(range) {
+            return;
+        }
+
+        self.val = self.val & Self::invert(Self::span_mask(range));
+    }
+
+    /// Flips all bits from `start` to `end` (inclusive)
+    #[inline]
+    pub fn toggle_range(&mut self, range: (u8, u8)) {
+        self.toggle_range_at(Self::widen(range));
+    }
+
+    /// Same as [`toggle_range`](Self::toggle_range) but takes the range as plain indices
+    #[inline]
+    pub fn toggle_range_at(&mut self, range: (usize, usize)) {
+        if !Self::is_valid_range(range) {
+            return;
+        }
+
+        self.val = self.val ^ Self::span_mask(range);
+    }
+
+    /// Clears all bits outside of `start` to `end` (inclusive). Returns `false` without changing
+    /// anything if the range is invalid
+    #[inline]
+    pub fn keep_only(&mut self, range: (u8, u8)) -> bool {
+        self.keep_only_at(Self::widen(range))
+    }
+
+    /// Same as [`keep_only`](Self::keep_only) but takes the range as plain indices
+    #[inline]
+    pub fn keep_only_at(&mut self, range: (usize, usize)) -> bool {
+        if !Self::is_valid_range(range) {
+            return false;
+        }
+
+        self.val = self.val & Self::span_mask(range);
+        true
+    }
+
+    /// Copies the bits from `start` to `end` (inclusive) of `src` to the bits starting at
+    /// `dst_start`. Overlapping ranges are handled like the source was copied to a temporary
+    /// first. Returns `false` without changing anything if the source range is invalid or the
+    /// destination range doesn't fit into T
+    #[inline]
+    pub fn copy_range(&mut self, src: (u8, u8), dst_start: u8) -> bool {
+        self.copy_range_at(Self::widen(src), dst_start as usize)
+    }
+
+    /// Same as [`copy_range`](Self::copy_range) but takes the range as plain indices
+    #[inline]
+    pub fn copy_range_at(&mut self, src: (usize, usize), dst_start: usize) -> bool {
+        if !Self::is_valid_range(src) {
+            return false;
+        }
+
+        if dst_start >= Self::size() || dst_start + (src.1 - src.0) >= Self::size() {
+            return false;
+        }
+
+        let dst = (dst_start, dst_start + (src.1 - src.0));
+
+        let field = Self::shift_down(self.val & Self::span_mask(src), src);
+        self.val =
+            (self.val & Self::invert(Self::span_mask(dst))) | (field << T::from_usize(dst.0));
+        true
+    }
+
+    /// Copies the bits from `start` to `end` (inclusive) of `other` into the same range of
+    /// `self`, leaving all other bits untouched
+    #[inline]
+    pub fn copy_from(&mut self, other: &BitFlag<T>, range: (u8, u8)) {
+        self.copy_from_at(other, Self::widen(range));
+    }
+
+    /// Same as [`copy_from`](Self::copy_from) but takes the range as plain indices
+    #[inline]
+    pub fn copy_from_at(&mut self, other: &BitFlag<T>, range: (usize, usize)) {
+        if !Self::is_valid_range(range) {
+            return;
+        }
+
+        let mask = Self::span_mask(range);
+        self.val = (self.val & Self::invert(mask)) | (other.val & mask);
+    }
+
+    /// Swaps the bits of the ranges `a` and `b` (inclusive). Returns `false` without changing
+    /// anything if any range is invalid, the ranges differ in length or they overlap
+    #[inline]
+    pub fn swap_ranges(&mut self, a: (u8, u8), b: (u8, u8)) -> bool {
+        self.swap_ranges_at(Self::widen(a), Self::widen(b))
+    }
+
+    /// Same as [`swap_ranges`](Self::swap_ranges) but takes the ranges as plain indices
+    #[inline]
+    pub fn swap_ranges_at(&mut self, a: (usize, usize), b: (usize, usize)) -> bool {
+        if !Self::is_valid_range(a) || !Self::is_valid_range(b) {
+            return false;
+        }
+
+        if a.1 - a.0 != b.1 - b.0 || (a.0 <= b.1 && b.0 <= a.1) {
+            return false;
+        }
+
+        let (mask_a, mask_b) = (Self::span_mask(a), Self::span_mask(b));
+        let field_a = Self::shift_down(self.val & mask_a, a);
+        let field_b = Self::shift_down(self.val & mask_b, b);
+
+        self.val = (self.val & Self::invert(mask_a | mask_b))
+            | (field_a << T::from_usize(b.0))
+            | (field_b << T::from_usize(a.0));
+        true
+    }
+
+    /// Reverses the order of the bits from `start` to `end` (inclusive), leaving all other bits
+    /// untouched
+    #[inline]
+    pub fn reverse_range(&mut self, range: (u8, u8)) {
+        self.reverse_range_at(Self::widen(range));
+    }
+
+    /// Same as [`reverse_range`](Self::reverse_range) but takes the range as plain indices
+    #[inline]
+    pub fn reverse_range_at(&mut self, range: (usize, usize)) {
+        if !Self::is_valid_range(range) {
+            return;
+        }
+
+        let mask = Self::span_mask(range);
+        // Reversing moves the field to the top, so shift it back down to the start of the range
+        let field = self.val.reverse_bits() >> T::from_usize(Self::size() - 1 - range.1);
+        let field = Self::shift_down(field, (0, range.1 - range.0)) << T::from_usize(range.0);
+        self.val = (self.val & Self::invert(mask)) | field;
+    }
+
+    /// Copies all bits set in `mask` from `other` into `self`, leaving all other bits untouched
+    #[inline]
+    pub fn assign_masked(&mut self, other: &Self, mask: &Self) {
+        self.val = (self.val & Self::invert(mask.val)) | (other.val & mask.val);
+    }
+
+    /// Gathers the bits selected by `mask` and packs them into the low bits of the result, in
+    /// order of their position (like PEXT)
+    #[inline]
+    pub fn extract(&self, mask: &Self) -> T {
+        let mut res = Self::new();
+
+        for (i, pos) in mask.iter_ones().enumerate() {
+            res.set_unchecked(T::from(i as u8), self.get_unchecked(T::from(pos as u8)));
+        }
+
+        res.val
+    }
+
+    /// Scatters the low bits of `value` into the positions selected by `mask`, in order of their
+    /// position (like PDEP). Bits outside of `mask` are left untouched
+    #[inline]
+    pub fn deposit(&mut self, mask: &Self, value: T) {
+        let value = Self::new_with_value(value);
+
+        for (i, pos) in mask.iter_ones().enumerate() {
+            self.set_unchecked(T::from(pos as u8), value.get_unchecked(T::from(i as u8)));
+        }
+    }
+
+    /// Get the value between `start` and `end` as T
+    #[inline]
+    pub fn get_range(&self, range: (u8, u8)) -> Option<T> {
+        self.get_range_at(Self::widen(range))
+    }
+
+    /// Get the value between `start` and `end` as T. Same as [`get_range`](Self::get_range) but
+    /// takes the range as plain indices
+    #[inline]
+    pub fn get_range_at(&self, range: (usize, usize)) -> Option<T> {
+        if !Self::is_valid_range(range) {
+            return None;
+        }
+
+        Some(self.read_span(range))
+    }
+
+    /// Converts the bitflag to a bitflag of type U, dropping all bits that don't fit. Use the
+    /// `TryFrom` impls to narrow without losing bits
+    #[inline]
+    pub fn truncate<U: BitflagAble>(&self) -> BitFlag<U> {
+        self.cast()
+    }
+
+    /// Get the value between `start` and `end` as U. Returns `None` if the range is invalid or
+    /// wider than U
+    #[inline]
+    pub fn get_range_as<U: BitflagAble>(&self, range: (u8, u8)) -> Option<U> {
+        self.get_range_as_at(Self::widen(range))
+    }
+
+    /// Same as [`get_range_as`](Self::get_range_as) but takes the range as plain indices
+    #[inline]
+    pub fn get_range_as_at<U: BitflagAble>(&self, range: (usize, usize)) -> Option<U> {
+        if !Self::is_valid_range(range) || Self::range_len(range) > BitFlag::<U>::size() {
+            return None;
+        }
+
+        Some(Self::new_with_value(self.read_span(range)).cast::<U>().val)
+    }
+
+    /// Get the value of the bits in `range` as T. Accepts all std range types, so `0..8` and `..`
+    /// both cover all bits of a `BitFlag<u8>`. Returns `None` for empty or overflowing ranges
+    #[inline]
+    pub fn get_bits<R: RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        self.get_range_at(Self::bounds_to_range(range)?)
+    }
+
+    /// Get the value between `start` and `end` as T, clipping `end` to the last bit. Returns the
+    /// value together with the number of bits read, which is 0 for reversed ranges
+    #[inline]
+    pub fn get_range_clamped(&self, range: (u8, u8)) -> (T, usize) {
+        self.get_range_clamped_at(Self::widen(range))
+    }
+
+    /// Same as [`get_range_clamped`](Self::get_range_clamped) but takes the range as plain indices
+    #[inline]
+    pub fn get_range_clamped_at(&self, range: (usize, usize)) -> (T, usize) {
+        match Self::clamp_range(range) {
+            Some(range) => (self.read_span(range), Self::range_len(range)),
+            None => (T::default(), 0),
+        }
+    }
+
+    /// Get the value between `start` and `end` as T. Fails if the range is reversed or overflows
+    #[inline]
+    pub fn try_get_range(&self, range: (u8, u8)) -> Result<T, BitFlagError> {
+        self.try_get_range_at(Self::widen(range))
+    }
+
+    /// Same as [`try_get_range`](Self::try_get_range) but takes the range as plain indices
+    #[inline]
+    pub fn try_get_range_at(&self, range: (usize, usize)) -> Result<T, BitFlagError> {
+        Self::check_range(range)?;
+        Ok(self.read_span(range))
+    }
+
+    /// Get the value between `start` and `end` as T unchecked
+    #[inline]
+    pub fn get_range_unchecked(&self, range: (u8, u8)) -> T {
+        self.get_range_at_unchecked(Self::widen(range))
+    }
+
+    /// Same as [`get_range_unchecked`](Self::get_range_unchecked)
+    /// but takes the range as plain indices
+    #[inline]
+    pub fn get_range_at_unchecked(&self, range: (usize, usize)) -> T {
+        self.read_span(range)
+    }
+
+    /// Get the value between `start` and `end` as T and clear the range afterwards
+    #[inline]
+    pub fn take_range(&mut self, range: (u8, u8)) -> Option<T> {
+        self.take_range_at(Self::widen(range))
+    }
+
+    /// Same as [`take_range`](Self::take_range) but takes the range as plain indices
+    #[inline]
+    pub fn take_range_at(&mut self, range: (usize, usize)) -> Option<T> {
+        if !Self::is_valid_range(range) {
+            return None;
+        }
+
+        let mask = Self::span_mask(range);
+        let val = Self::shift_down(self.val & mask, range);
+        self.val = self.val & Self::invert(mask);
+        Some(val)
+    }
+
+    /// Returns `true` if any bit from `start` to `end` (inclusive) is set. Returns `false` if the
+    /// range is invalid
+    #[inline]
+    pub fn any_set_in(&self, range: (u8, u8)) -> bool {
+        self.any_set_in_at(Self::widen(range))
+    }
+
+    /// Same as [`any_set_in`](Self::any_set_in) but takes the range as plain indices
+    #[inline]
+    pub fn any_set_in_at(&self, range: (usize, usize)) -> bool {
+        Self::is_valid_range(range) && (self.val & Self::span_mask(range)) != T::from(0u8)
+    }
+
+    /// Returns `true` if all bits from `start` to `end` (inclusive) are set. Returns `false` if
+    /// the range is invalid
+    #[inline]
+    pub fn all_set_in(&self, range: (u8, u8)) -> bool {
+        self.all_set_in_at(Self::widen(range))
+    }
+
+    /// Same as [`all_set_in`](Self::all_set_in) but takes the range as plain indices
+    #[inline]
+    pub fn all_set_in_at(&self, range: (usize, usize)) -> bool {
+        if !Self::is_valid_range(range) {
+            return false;
+        }
+
+        let mask = Self::span_mask(range);
+        (self.val & mask) == mask
+    }
+
+    /// Gets a bit at the given `pos`. Returns `false` if `pos` would cause an overflow, use
+    /// [`try_get`](Self::try_get) for computed positions to tell both cases apart
+    #[inline]
+    pub fn get(&self, pos: T) -> bool {
+        if Self::is_overflow(pos) {
+            #[cfg(feature = "strict")]
+            Self::strict_overflow(pos);
+            return false;
+        }
+
+        self.get_unchecked(pos)
+    }
+
+    /// Gets a bit at the given [`pos`]
+    #[inline]
+    pub fn get_unchecked(&self, pos: T) -> bool {
+        let mask = T::from(1u8) << pos;
+        (self.val & mask) != T::from(0u8)
+    }
+
+    /// Gets the byte at the given `index`, where index 0 is the least significant byte. Returns
+    /// `None` if `index` is out of range
+    #[inline]
+    pub fn get_byte(&self, index: usize) -> Option<u8> {
+        if index >= Self::size() / 8 {
+            return None;
+        }
+
+        Some((self.val >> T::from((index * 8) as u8)).low_byte())
+    }
+
+    /// Sets the byte at the given `index` to `byte`, where index 0 is the least significant byte.
+    /// Returns `false` without changing anything if `index` is out of range
+    #[inline]
+    pub fn set_byte(&mut self, index: usize, byte: u8) -> bool {
+        if index >= Self::size() / 8 {
+            return false;
+        }
+
+        let shift = T::from((index * 8) as u8);
+        let mask = T::from(0xFF) << shift;
+        self.val = (self.val & Self::invert(mask)) | (T::from(byte) << shift);
+        true
+    }
+
+    /// Get the raw value of the bitflag
+    #[inline]
+    pub fn raw(&self) -> T {
+        self.val
+    }
+
+    /// Consumes the bitflag and returns its raw value
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.val
+    }
+
+    /// Get a mutable reference to the raw value of the bitflag
+    #[inline]
+    pub fn raw_mut(&mut self) -> &mut T {
+        &mut self.val
+    }
+
+    /// Clears the value to `T::default()`
+    #[inline]
+    pub fn clear(&mut self) {
+        self.val = T::default();
+    }
+
+    /// Sets all bits to `val`
+    #[inline]
+    pub fn fill(&mut self, val: bool) {
+        if val {
+            *self = Self::all();
+        } else {
+            self.clear();
+        }
+    }
+
+    /// Returns true if [`pos`] would cause an overflow
+    #[inline]
+    pub fn is_overflow(pos: T) -> bool {
+        pos >= T::from(Self::size() as u8)
+    }
+
+    /// Returns the amonut of bits set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.val.count_ones() as usize
+    }
+
+    /// Returns the amount of bits set. Same as [`len`](Self::len)
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the amount of bits not set
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        Self::size() - self.count_ones()
+    }
+
+    /// Returns `true` if at least one bit is set.
+    #[inline]
+    pub fn any(&self) -> bool {
+        !self.is_empty()
+    }
+
+    /// Returns `true` if an odd amount of bits is set
+    #[inline]
+    pub fn parity(&self) -> bool {
+        self.val.count_ones() & 1 == 1
+    }
+
+    /// Returns `true` if an odd amount of bits from `start` to `end` (inclusive) is set. Returns
+    /// `false` if the range is invalid
+    #[inline]
+    pub fn parity_in_range(&self, range: (u8, u8)) -> bool {
+        self.parity_in_range_at(Self::widen(range))
+    }
+
+    /// Same as [`parity_in_range`](Self::parity_in_range) but takes the range as plain indices
+    #[inline]
+    pub fn parity_in_range_at(&self, range: (usize, usize)) -> bool {
+        Self::is_valid_range(range) && (self.val & Self::span_mask(range)).count_ones() & 1 == 1
+    }
+
+    /// Returns `true` if there is no bit set. Same as [`is_empty`](Self::is_empty)
+    #[inline]
+    pub fn none(&self) -> bool {
+        self.is_empty()
+    }
+
+    /// Returns `true` if all bits are set. Same as [`is_full`](Self::is_full), as
+    /// [`all`](Self::all) already creates a full BitFlag
+    #[inline]
+    pub fn all_set(&self) -> bool {
+        self.is_full()
+    }
+
+    /// Returns `true` if all bits set in `other` are also set in `self`
+    #[inline]
+    pub fn contains_all<V: Into<BitFlag<T>>>(&self, other: V) -> bool {
+        let other = other.into();
+        (self.val & other.val) == other.val
+    }
+
+    /// Returns `true` if any bit set in `other` is also set in `self`
+    #[inline]
+    pub fn contains_any<V: Into<BitFlag<T>>>(&self, other: V) -> bool {
+        (self.val & other.into().val) != T::from(0u8)
+    }
+
+    /// Returns `true` if all bits set in `self` are also set in `other`
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        (self.val & other.val) == self.val
+    }
+
+    /// Returns `true` if all bits set in `other` are also set in `self`
+    #[inline]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` and `other` have no bits set in common
+    #[inline]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        (self.val & other.val) == T::from(0u8)
+    }
+
+    /// Returns the amount of positions at which `self` and `other` differ
+    #[inline]
+    pub fn hamming_distance(&self, other: &Self) -> usize {
+        (self.val ^ other.val).count_ones() as usize
+    }
+
+    /// Returns the lowest position at which `self` and `other` differ or `None` if they are equal
+    #[inline]
+    pub fn first_difference(&self, other: &Self) -> Option<usize> {
+        Self::new_with_value(self.val ^ other.val).first_set()
+    }
+
+    /// Returns the amount of positions set in both `self` and `other`
+    #[inline]
+    pub fn overlap_count(&self, other: &Self) -> usize {
+        (self.val & other.val).count_ones() as usize
+    }
+
+    /// Returns the amount of trailing zeros. Returns [`size`](Self::size) if no bit is set
+    #[inline]
+    pub fn trailing_zeros(&self) -> usize {
+        self.val.trailing_zeros() as usize
+    }
+
+    /// Returns the position of the lowest bit set or `None` if no bit is set
+    #[inline]
+    pub fn first_set(&self) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.trailing_zeros())
+    }
+
+    /// Returns the position of the lowest bit not set or `None` if all bits are set
+    #[inline]
+    pub fn first_unset(&self) -> Option<usize> {
+        let pos = Self::invert(self.val).trailing_zeros() as usize;
+        if pos >= Self::size() {
+            return None;
+        }
+
+        Some(pos)
+    }
+
+    /// Returns the position of the lowest bit set that is strictly greater than `pos` or `None`
+    /// if there is no such bit
+    #[inline]
+    pub fn next_set_after(&self, pos: T) -> Option<usize> {
+        let above = Self::mask_above(pos)?;
+        Self::new_with_value(self.val & above).first_set()
+    }
+
+    /// Returns the position of the lowest bit not set that is strictly greater than `pos` or
+    /// `None` if there is no such bit
+    #[inline]
+    pub fn next_unset_after(&self, pos: T) -> Option<usize> {
+        let above = Self::mask_above(pos)?;
+        Self::new_with_value(self.val | Self::invert(above)).first_unset()
+    }
+
+    /// Returns the position of the `n`-th (0 based) bit set or `None` if less than `n + 1` bits
+    /// are set
+    #[inline]
+    pub fn nth_set(&self, mut n: usize) -> Option<usize> {
+        // Skip whole bytes until the byte containing the n-th set bit is found
+        for byte in 0..Self::size() / 8 {
+            let offset = byte * 8;
+            let ones = ((self.val >> T::from(offset as u8)) & T::from(0xFF)).count_ones() as usize;
+
+            if n >= ones {
+                n -= ones;
+                continue;
+            }
+
+            return (offset..offset + 8)
+                .filter(|i| self.get_unchecked(T::from(*i as u8)))
+                .nth(n);
+        }
+
+        None
+    }
+
+    /// Returns the amount of bits set at positions strictly below `pos`
+    #[inline]
+    pub fn rank(&self, pos: T) -> usize {
+        if Self::is_overflow(pos) {
+            return self.count_ones();
+        }
+
+        (self.val & Self::mask_below(pos)).count_ones() as usize
+    }
+
+    /// Returns the amount of leading zeros, counted from the most significant bit. Returns
+    /// [`size`](Self::size) if no bit is set
+    #[inline]
+    pub fn leading_zeros(&self) -> usize {
+        self.val.leading_zeros() as usize
+    }
+
+    /// Returns the position of the highest bit set or `None` if no bit is set. Positions are
+    /// counted from the least significant bit, starting at 0
+    #[inline]
+    pub fn last_set(&self) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(Self::size() - 1 - self.leading_zeros())
+    }
+
+    /// Returns a copy with all bits flipped
+    #[inline]
+    pub fn complement(&self) -> Self {
+        Self::new_with_value(self.val ^ Self::all().val)
+    }
+
+    /// Flips all bits
+    #[inline]
+    pub fn complement_in_place(&mut self) {
+        *self = self.complement();
+    }
+
+    /// Adds the raw values of both bitflags, returning `None` if the result overflows. Like the
+    /// `Add` operator this is arithmetic and carries over bits set in both operands, use `|` for
+    /// the union. Signed types are added as their unsigned counterparts
+    #[inline]
+    pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        self.val.checked_add(rhs.val).map(Self::new_with_value)
+    }
+
+    /// Adds the raw values of both bitflags, wrapping around on overflow. Signed types are added
+    /// as their unsigned counterparts
+    #[inline]
+    pub fn wrapping_add(&self, rhs: Self) -> Self {
+        Self::new_with_value(self.val.wrapping_add(rhs.val))
+    }
+
+    /// Adds the raw values of both bitflags, resulting in a full bitflag on overflow. Signed
+    /// types are added as their unsigned counterparts
+    #[inline]
+    pub fn saturating_add(&self, rhs: Self) -> Self {
+        Self::new_with_value(self.val.saturating_add(rhs.val))
+    }
+
+    /// Returns a copy with the order of bits reversed, so bit 0 becomes bit `size() - 1`
+    #[inline]
+    pub fn reverse_bits(&self) -> Self {
+        Self::new_with_value(self.val.reverse_bits())
+    }
+
+    /// Reverses the order of bits, so bit 0 becomes bit `size() - 1`
+    #[inline]
+    pub fn reverse_bits_in_place(&mut self) {
+        self.val = self.val.reverse_bits();
+    }
+
+    /// Returns a copy with the bits shifted to the left by `n`, wrapping the truncated bits to
+    /// the end. `n` is taken modulo [`size`](Self::size)
+    #[inline]
+    pub fn rotate_left(&self, n: u32) -> Self {
+        Self::new_with_value(self.val.rotate_left(n))
+    }
+
+    /// Returns a copy with the bits shifted to the right by `n`, wrapping the truncated bits to
+    /// the beginning. `n` is taken modulo [`size`](Self::size)
+    #[inline]
+    pub fn rotate_right(&self, n: u32) -> Self {
+        Self::new_with_value(self.val.rotate_right(n))
+    }
+
+    /// Shifts the bits to the left by `n`, wrapping the truncated bits to the end
+    #[inline]
+    pub fn rotate_left_in_place(&mut self, n: u32) {
+        self.val = self.val.rotate_left(n);
+    }
+
+    /// Shifts the bits to the right by `n`, wrapping the truncated bits to the beginning
+    #[inline]
+    pub fn rotate_right_in_place(&mut self, n: u32) {
+        self.val = self.val.rotate_right(n);
+    }
+
+    /// Returns a copy with the order of bytes reversed
+    #[inline]
+    pub fn swap_bytes(&self) -> Self {
+        Self::new_with_value(self.val.swap_bytes())
+    }
+
+    /// Returns a copy converted to big endian from the target's endianness
+    #[inline]
+    pub fn to_be(&self) -> Self {
+        Self::new_with_value(self.val.to_be())
+    }
+
+    /// Returns a copy converted to little endian from the target's endianness
+    #[inline]
+    pub fn to_le(&self) -> Self {
+        Self::new_with_value(self.val.to_le())
+    }
+
+    /// Returns `true` if there is no bit set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        T::from(0u8) == self.val
+    }
+
+    /// Returns `true` if all bits are set.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        Self::invert(T::from(0u8)) == self.val
+    }
+
+    /// Returns a mutable handle to the bit at `pos` or `None` if `pos` would cause an overflow
+    #[inline]
+    pub fn at(&mut self, pos: T) -> Option<BitRef<'_, T>> {
+        if Self::is_overflow(pos) {
+            return None;
+        }
+
+        Some(BitRef::new(self, pos))
+    }
+
+    /// Returns an iterator over all fields of the bitflag.
+    #[inline]
+    pub fn iter(&self) -> BitIter<T> {
+        BitIter::new(*self)
+    }
+
+    /// Returns an iterator over the positions of all bits set.
+    #[inline]
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> {
+        let mut val = self.val;
+        std::iter::from_fn(move || {
+            if val == T::from(0u8) {
+                return None;
+            }
+
+            // Take the lowest set bit and clear it
+            let pos = val.trailing_zeros() as u8;
+            val = val ^ (T::from(1u8) << T::from(pos));
+            Some(pos as usize)
+        })
+    }
+
+    /// Returns an iterator over the positions of all bits not set.
+    #[inline]
+    pub fn iter_zeros(&self) -> impl Iterator<Item = usize> {
+        Self::new_with_value(Self::invert(self.val)).iter_ones()
+    }
+
+    /// Returns an iterator over all positions at which `self` and `other` differ, together with
+    /// the value of the bit in `other`.
+    #[inline]
+    pub fn diff(&self, other: &Self) -> impl Iterator<Item = (usize, bool)> {
+        let other = *other;
+        Self::new_with_value(self.val ^ other.val)
+            .iter_ones()
+            .map(move |pos| (pos, other.get_unchecked(T::from(pos as u8))))
+    }
+
+    /// Returns the binary representation padded with zeros to [`size`](Self::size) characters
+    #[inline]
+    pub fn to_padded_string(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Returns a value displaying the binary representation with a `_` inserted between every
+    /// `group` digits, counted from the least significant bit. A `group` of 0 disables grouping
+    #[inline]
+    pub fn display_grouped(&self, group: usize) -> impl Display {
+        GroupedDisplay { flag: *self, group }
+    }
+
+    /// Returns the amonut of bits that can be accessed for the given base type T
+    #[inline]
+    pub fn size() -> usize {
+        std::mem::size_of::<T>() * 8
+    }
+
+    /// Returns `true` if `start <= end` and `end` doesn't cause an overflow
+    #[inline]
+    fn is_valid_range(range: (usize, usize)) -> bool {
+        range.0 <= range.1 && range.1 < Self::size()
+    }
+
+    /// Copies all bits into a bitflag of type U, dropping bits that don't fit
+    #[inline]
+    fn cast<U: BitflagAble>(self) -> BitFlag<U> {
+        self.iter_ones()
+            .take_while(|pos| *pos < BitFlag::<U>::size())
+            .map(U::from_usize)
+            .collect()
+    }
+
+    /// Converts a range of u8 positions to the range of indices used internally
+    #[inline]
+    fn widen(range: (u8, u8)) -> (usize, usize) {
+        (range.0 as usize, range.1 as usize)
+    }
+
+    /// Writes the low bits of `val` to the bits from `start` to `end` (inclusive). The range
+    /// must be valid
+    #[inline]
+    fn write_span(&mut self, range: (usize, usize), val: BitFlag<T>) {
+        for (i, flag_pos) in (range.0..=range.1).enumerate() {
+            self.set_unchecked(T::from_usize(flag_pos), val.get_unchecked(T::from_usize(i)));
+        }
+    }
+
+    /// Reads the bits from `start` to `end` (inclusive) into the low bits of the result. The
+    /// range must be valid
+    #[inline]
+    fn read_span(&self, range: (usize, usize)) -> T {
+        let mut cpy: BitFlag<T> = BitFlag::new();
+
+        for (i, flag_pos) in (range.0..=range.1).enumerate() {
+            cpy.set_unchecked(
+                T::from_usize(i),
+                self.get_unchecked(T::from_usize(flag_pos)),
+            );
+        }
+
+        cpy.val
+    }
+
+    /// Converts `bounds` to an inclusive range or `None` if the range is empty. Unbounded ends
+    /// are mapped to the last bit
+    #[inline]
+    fn bounds_to_range<R: RangeBounds<usize>>(bounds: R) -> Option<(usize, usize)> {
+        let start = match bounds.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match bounds.end_bound() {
+            Bound::Included(end) => *end,
+            Bound::Excluded(end) => end.checked_sub(1)?,
+            Bound::Unbounded => Self::size() - 1,
+        };
+
+        if start > end {
+            return None;
+        }
+
+        Some((start, end))
+    }
+
+    /// Converts the index `pos` to a position or `None` if it would cause an overflow
+    #[inline]
+    fn position(pos: usize) -> Option<T> {
+        if pos >= Self::size() {
+            return None;
+        }
+
+        Some(T::from_usize(pos))
+    }
+
+    /// Clips the end of `range` to the last bit. Returns `None` if nothing of the range is left
+    #[inline]
+    fn clamp_range(range: (usize, usize)) -> Option<(usize, usize)> {
+        let range = (range.0, range.1.min(Self::size() - 1));
+        if !Self::is_valid_range(range) {
+            return None;
+        }
+
+        Some(range)
+    }
+
+    /// Returns the amount of bits in `range`. The range must be valid
+    #[inline]
+    fn range_len(range: (usize, usize)) -> usize {
+        range.1 - range.0 + 1
+    }
+
+    /// Returns an error describing why `range` is invalid
+    #[inline]
+    fn check_range(range: (usize, usize)) -> Result<(), BitFlagError> {
+        if range.0 > range.1 {
+            return Err(BitFlagError::InvalidRange {
+                start: range.0,
+                end: range.1,
+            });
+        }
+
+        if range.1 >= Self::size() {
+            return Err(BitFlagError::PositionOutOfRange {
+                pos: range.1,
+                width: Self::size(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the error for an overflowing `pos`
+    #[inline]
+    fn out_of_range(pos: T) -> BitFlagError {
+        BitFlagError::PositionOutOfRange {
+            pos: pos.to_usize(),
+            width: Self::size(),
+        }
+    }
+
+    /// Panics with the offending position. Used by the `strict` feature
+    #[cfg(feature = "strict")]
+    #[cold]
+    fn strict_overflow(pos: T) {
+        panic!(
+            "position {} is out of range for a bitflag of {} bits",
+            pos,
+            Self::size()
+        );
+    }
+
+    /// Panics with the offending range. Used by the `strict` feature
+    #[cfg(feature = "strict")]
+    #[cold]
+    fn strict_invalid_range(range: (usize, usize)) {
+        panic!(
+            "invalid range ({}, {}) for a bitflag of {} bits",
+            range.0,
+            range.1,
+            Self::size()
+        );
+    }
+
+    /// Returns a mask with all bits from `start` to `end` (inclusive) set. The range must be valid
+    #[inline]
+    fn span_mask(range: (usize, usize)) -> T {
+        // Shift twice so `end == size() - 1` doesn't overflow
+        let upto_end = Self::invert((Self::all().val << T::from_usize(range.1)) << T::from(1u8));
+        upto_end & (Self::all().val << T::from_usize(range.0))
+    }
+
+    /// Shifts the bits from `start` to `end` (inclusive) of `val` down to position 0 and clears
+    /// everything else. The range must be valid
+    #[inline]
+    fn shift_down(val: T, range: (usize, usize)) -> T {
+        // Mask again after shifting as signed types shift in ones
+        (val >> T::from_usize(range.0)) & Self::span_mask((0, range.1 - range.0))
+    }
+
+    /// Returns a mask with all bits at the given `positions` set, skipping overflowing positions
+    #[inline]
+    fn positions_mask(positions: &[T]) -> T {
+        positions
+            .iter()
+            .filter(|pos| !Self::is_overflow(**pos))
+            .fold(T::from(0u8), |mask, pos| mask | (T::from(1u8) << *pos))
+    }
+
+    /// Returns a mask with all bits above `pos` set or `None` if there are no such bits
+    #[inline]
+    fn mask_above(pos: T) -> Option<T> {
+        if pos >= T::from((Self::size() - 1) as u8) {
+            return None;
+        }
+
+        Some(Self::all().val << (pos + T::from(1u8)))
+    }
+
+    /// Returns a mask with all bits below `pos` set. `pos` must not overflow
+    #[inline]
+    fn mask_below(pos: T) -> T {
+        Self::invert(Self::all().val << pos)
+    }
+
+    ///  Inverts all bits in [`val`]
+    #[inline]
+    fn invert(val: T) -> T {
+        (!val) as T
+    }
+}
+
+/// Formats the value in binary. The alternate form (`{:#?}`) lists the width and the positions of
+/// all bits set instead, e.g. `BitFlag { bits: 64, set: [3, 17, 40] }`.
+impl<T: BitflagAble> Debug for BitFlag<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            let set: Vec<_> = self.iter_ones().collect();
+            return write!(f, "BitFlag {{ bits: {}, set: {:?} }}", Self::size(), set);
+        }
+
+        write!(f, "{}", self)
+    }
+}
+
+/// Formats the value in binary. The alternate form (`{:#}`) pads the value with zeros to the full
+/// width of T.
+impl<T: BitflagAble> Display for BitFlag<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return write!(f, "{:0width$b}", self.val, width = Self::size());
+        }
+
+        write!(f, "{:b}", self.val)
+    }
+}
+
+impl<T: BitflagAble> Binary for BitFlag<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Binary::fmt(&self.val, f)
+    }
+}
+
+impl<T: BitflagAble> LowerHex for BitFlag<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        LowerHex::fmt(&self.val, f)
+    }
+}
+
+impl<T: BitflagAble> UpperHex for BitFlag<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        UpperHex::fmt(&self.val, f)
+    }
+}
+
+impl<T: BitflagAble> Octal for BitFlag<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Octal::fmt(&self.val, f)
+    }
+}
+
+/// Binary representation of a BitFlag with separators. See [`BitFlag::display_grouped`].
+struct GroupedDisplay<T> {
+    flag: BitFlag<T>,
+    group: usize,
+}
+
+impl<T: BitflagAble> Display for GroupedDisplay<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = format!("{:b}", self.flag.val);
+        if self.group == 0 {
+            return f.write_str(&digits);
+        }
+
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % self.group == 0 {
+                f.write_str("_")?;
+            }
+            write!(f, "{}", digit)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds the raw values of both bitflags.
+///
+/// **This is arithmetic, not the union!** Bits set in both operands carry over into the next
+/// higher bit, e.g. `0b01 + 0b11 == 0b100`. Use `|` to combine bitflags and
+/// [`BitFlag::checked_add`] to detect overflows.
+impl<T: BitflagAble> Add<Self> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new_with_value(self.val.add(rhs.val))
+    }
+}
+
+impl<T: BitflagAble, U: BitflagAble + Add<T, Output = T>> Add<U> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: U) -> Self::Output {
+        let v = rhs.add(self.val);
+        Self::new_with_value(v)
+    }
+}
+
+impl<T: BitflagAble, U: BitflagAble + Add<T, Output = T>> AddAssign<U> for BitFlag<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: U) {
+        self.val = rhs.add(self.val)
+    }
+}
+
+impl<T: BitflagAble> AddAssign<Self> for BitFlag<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.val = self.val + rhs.val;
+    }
+}
+
+impl<T: BitflagAble> BitOr<Self> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::new_with_value(self.val | rhs.val)
+    }
+}
+
+impl<T: BitflagAble> BitOrAssign<Self> for BitFlag<T> {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.val = self.val | rhs.val;
+    }
+}
+
+impl<T: BitflagAble> BitAnd<Self> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self::new_with_value(self.val & rhs.val)
+    }
+}
+
+impl<T: BitflagAble> BitAndAssign<Self> for BitFlag<T> {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.val = self.val & rhs.val;
+    }
+}
+
+impl<T: BitflagAble> BitXor<Self> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self::new_with_value(self.val ^ rhs.val)
+    }
+}
+
+impl<T: BitflagAble> BitXorAssign<Self> for BitFlag<T> {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.val = self.val ^ rhs.val;
+    }
+}
+
+impl<T: BitflagAble> BitOr<T> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: T) -> Self::Output {
+        Self::new_with_value(self.val | rhs)
+    }
+}
+
+impl<T: BitflagAble> BitOrAssign<T> for BitFlag<T> {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: T) {
+        self.val = self.val | rhs;
+    }
+}
+
+impl<T: BitflagAble> BitAnd<T> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: T) -> Self::Output {
+        Self::new_with_value(self.val & rhs)
+    }
+}
+
+impl<T: BitflagAble> BitAndAssign<T> for BitFlag<T> {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: T) {
+        self.val = self.val & rhs;
+    }
+}
+
+impl<T: BitflagAble> BitXor<T> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: T) -> Self::Output {
+        Self::new_with_value(self.val ^ rhs)
+    }
+}
+
+impl<T: BitflagAble> BitXorAssign<T> for BitFlag<T> {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: T) {
+        self.val = self.val ^ rhs;
+    }
+}
+
+/// Removes all bits set in `rhs`, so `a - b == a & !b`. This is not an arithmetic subtraction.
+impl<T: BitflagAble> Sub<Self> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new_with_value(self.val & Self::invert(rhs.val))
+    }
+}
+
+/// Removes all bits set in `rhs`, so `a - b == a & !b`. This is not an arithmetic subtraction.
+impl<T: BitflagAble> Sub<T> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: T) -> Self::Output {
+        Self::new_with_value(self.val & Self::invert(rhs))
+    }
+}
+
+impl<T: BitflagAble> SubAssign<Self> for BitFlag<T> {
+    #[inline]
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.val = self.val & Self::invert(rhs.val);
+    }
+}
+
+impl<T: BitflagAble> SubAssign<T> for BitFlag<T> {
+    #[inline]
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn sub_assign(&mut self, rhs: T) {
+        self.val = self.val & Self::invert(rhs);
+    }
+}
+
+/// Inverts all bits within the width of T.
+impl<T: BitflagAble> Not for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+/// Shifts all bits to the left by `rhs`, filling with zeros. Shifting by [`BitFlag::size`] or
+/// more results in an empty BitFlag.
+impl<T: BitflagAble> Shl<u32> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn shl(self, rhs: u32) -> Self::Output {
+        if rhs as usize >= Self::size() {
+            return Self::new();
+        }
+
+        Self::new_with_value(self.val << T::from(rhs as u8))
+    }
+}
+
+impl<T: BitflagAble> ShlAssign<u32> for BitFlag<T> {
+    #[inline]
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = *self << rhs;
+    }
+}
+
+/// Shifts all bits to the right by `rhs`, filling with zeros (even for signed types). Shifting by
+/// [`BitFlag::size`] or more results in an empty BitFlag.
+impl<T: BitflagAble> Shr<u32> for BitFlag<T> {
+    type Output = Self;
+
+    #[inline]
+    fn shr(self, rhs: u32) -> Self::Output {
+        if rhs as usize >= Self::size() {
+            return Self::new();
+        }
+
+        let range = (rhs as usize, Self::size() - 1);
+        Self::new_with_value(Self::shift_down(self.val, range))
+    }
+}
+
+impl<T: BitflagAble> ShrAssign<u32> for BitFlag<T> {
+    #[inline]
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = *self >> rhs;
+    }
+}
+
+static TRUE: bool = true;
+static FALSE: bool = false;
+
+/// Returns the bit at the given position.
+///
+/// # Panics
+///
+/// Panics if `index` is greater or equal than [`BitFlag::size`]. Use [`BitFlag::get`] for an
+/// unchecked lookup
+impl<T: BitflagAble> Index<usize> for BitFlag<T> {
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        assert!(
+            index < Self::size(),
+            "bit index {} out of range for a bitflag of {} bits",
+            index,
+            Self::size()
+        );
+
+        if self.get_unchecked(T::from(index as u8)) {
+            &TRUE
+        } else {
+            &FALSE
+        }
+    }
+}
+
+impl<T: BitflagAble> PartialEq<T> for BitFlag<T> {
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        self.val == *other
+    }
+}
+
+impl<T: BitflagAble> PartialOrd<T> for BitFlag<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        self.val.partial_cmp(other)
+    }
+}
+
+macro_rules! impl_raw_cmp {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq<BitFlag<$t>> for $t {
+                #[inline]
+                fn eq(&self, other: &BitFlag<$t>) -> bool {
+                    *self == other.val
+                }
+            }
+
+            impl PartialOrd<BitFlag<$t>> for $t {
+                #[inline]
+                fn partial_cmp(&self, other: &BitFlag<$t>) -> Option<Ordering> {
+                    self.partial_cmp(&other.val)
+                }
+            }
+        )*
+    };
+}
+
+impl_raw_cmp!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+
+impl<T: BitflagAble> From<T> for BitFlag<T> {
+    #[inline]
+    fn from(t: T) -> Self {
+        Self { val: t }
+    }
+}
+
+macro_rules! impl_from_bitflag {
+    ($($t:ty),*) => {
+        $(
+            impl From<BitFlag<$t>> for $t {
+                #[inline]
+                fn from(bf: BitFlag<$t>) -> Self {
+                    bf.into_inner()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_bitflag!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+
+impl<T: BitflagAble> AsRef<T> for BitFlag<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.val
+    }
+}
+
+/// BitFlags hash and compare exactly like their raw value, so they can be used to look up
+/// entries of maps keyed by T.
+impl<T: BitflagAble> Borrow<T> for BitFlag<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.val
+    }
+}
+
+#[cfg(feature = "with_serde")]
+impl<T> serde::Serialize for BitFlag<T>
+where
+    T: serde::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.val.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "with_serde")]
+impl<'a, T> serde::Deserialize<'a> for BitFlag<T>
+where
+    T: serde::Deserialize<'a>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let val = T::deserialize(deserializer)?;
+        Ok(BitFlag { val })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn check_overflow() {
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        assert!(bf8.is_empty());
+        // Set won't do anything as we're overflowing on purpose
+        bf8.set(8, true);
+        assert!(!bf8.get(8));
+    }
+
+    #[test]
+    fn set_all_false_u8() {
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        for i in 0..7 {
+            bf8.set(i, false);
+            assert!(!bf8.get(i));
+        }
+        assert!(bf8.is_empty());
+    }
+
+    #[test]
+    fn set_all_true_u8() {
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        for i in 0..7 {
+            bf8.set(i, true);
+            assert!(bf8.get(i));
+        }
+        assert!(!bf8.is_empty());
+    }
+
+    #[test]
+    fn toggle_all_u8() {
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        for i in 0..7 {
+            bf8.set(i, true);
+            assert!(bf8.get(i));
+
+            bf8.set(i, false);
+            assert!(!bf8.get(i));
+        }
+        assert!(bf8.is_empty());
+    }
+
+    #[test]
+    fn set_all_false_u32() {
+        let mut bf8: BitFlag<u32> = BitFlag::new();
+        for i in 0..31 {
+            bf8.set(i, false);
+            assert!(!bf8.get(i));
+        }
+    }
+
+    #[test]
+    fn set_all_true_u32() {
+        let mut bf8: BitFlag<u32> = BitFlag::new();
+        for i in 0..31 {
+            bf8.set(i, true);
+            assert!(bf8.get(i));
+        }
+    }
+
+    #[test]
+    fn toggle_all_u32() {
+        let mut bf8: BitFlag<u32> = BitFlag::new();
         for i in 0..31 {
             bf8.set(i, true);
-            assert_eq!(bf8.get(i), true);
+            assert!(bf8.get(i));
+
+            bf8.set(i, false);
+            assert!(!bf8.get(i));
+        }
+        assert!(bf8.is_empty());
+    }
+
+    #[test]
+    fn test_set_range() {
+        let mut bf: BitFlag<u32> = BitFlag::new();
+        let max = u32::MAX;
+
+        bf.set_range((3, 6), max);
+        assert_eq!(bf.raw(), 0b01111000);
+
+        bf.clear();
+
+        bf.set_range((28, 31), max);
+        assert_eq!(bf.raw(), 0b11110000000000000000000000000000);
+
+        bf.clear();
+
+        let e: u32 = 0b1011;
+        let e: BitFlag<u32> = BitFlag::new_with_value(e);
+        bf.set_range((28, 31), e);
+        assert_eq!(bf.raw(), 0b10110000000000000000000000000000);
+        assert!(!bf.is_empty());
+    }
+
+    #[test]
+    fn test_get_range() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b101110);
+        assert_eq!(bf.get_range((3, 0)), None);
+
+        assert_eq!(bf.get_range((1, 2)), Some(0b11));
+
+        assert_eq!(bf.get_range((0, 3)), Some(0b1110));
+
+        assert_eq!(bf.get_range((0, 4)), Some(0b01110));
+
+        assert_eq!(bf.get_range((4, 5)), Some(0b10));
+
+        assert_eq!(bf.get_range((0, 5)), Some(0b101110));
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        bf8.toggle(7);
+        assert_eq!(bf8.raw(), 0b10000000);
+        bf8.toggle(7);
+        assert!(bf8.is_empty());
+
+        let mut bf32: BitFlag<u32> = BitFlag::new_with_value(0b1010);
+        bf32.toggle(31);
+        assert!(bf32.get(31));
+        bf32.toggle(31);
+        assert_eq!(bf32.raw(), 0b1010);
+
+        let mut bf128: BitFlag<u128> = BitFlag::new_with_value(0b1010);
+        bf128.toggle(127);
+        assert_eq!(bf128.raw(), 1 << 127 | 0b1010);
+        bf128.toggle(127);
+        assert_eq!(bf128.raw(), 0b1010);
+
+        // Overflowing positions are ignored
+        bf8.toggle(8);
+        assert!(bf8.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_unchecked() {
+        let mut bf8: BitFlag<u8> = BitFlag::new_with_value(0b1);
+        bf8.toggle_unchecked(7);
+        assert_eq!(bf8.raw(), 0b1000_0001);
+        bf8.toggle_unchecked(7);
+        assert_eq!(bf8.raw(), 0b1);
+
+        let mut bf32: BitFlag<u32> = BitFlag::new_with_value(0b1010);
+        bf32.toggle_unchecked(31);
+        assert_eq!(bf32.raw(), 1 << 31 | 0b1010);
+        bf32.toggle_unchecked(31);
+        assert_eq!(bf32.raw(), 0b1010);
+
+        let mut bf128: BitFlag<u128> = BitFlag::all();
+        bf128.toggle_unchecked(127);
+        assert_eq!(bf128.raw(), u128::MAX >> 1);
+        bf128.toggle_unchecked(127);
+        assert!(bf128.is_full());
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        bf8.fill(true);
+        assert!(bf8.is_full());
+        assert_eq!(bf8.raw(), u8::MAX);
+        bf8.fill(false);
+        assert!(bf8.is_empty());
+        assert!(!bf8.is_full());
+
+        let mut bf16: BitFlag<u16> = BitFlag::new();
+        bf16.fill(true);
+        assert_eq!(bf16.raw(), u16::MAX);
+
+        let mut bf32: BitFlag<u32> = BitFlag::new_with_value(0b1010);
+        bf32.fill(true);
+        assert_eq!(bf32.raw(), u32::MAX);
+
+        let mut bf64: BitFlag<u64> = BitFlag::new();
+        bf64.fill(true);
+        assert_eq!(bf64.raw(), u64::MAX);
+
+        let mut bf128: BitFlag<u128> = BitFlag::new();
+        bf128.fill(true);
+        assert!(bf128.is_full());
+        bf128.set(127, false);
+        assert!(!bf128.is_full());
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(BitFlag::<u8>::all().raw(), u8::MAX);
+        assert_eq!(BitFlag::<u16>::all().raw(), u16::MAX);
+        assert_eq!(BitFlag::<u32>::all().raw(), u32::MAX);
+        assert_eq!(BitFlag::<u64>::all().raw(), u64::MAX);
+        assert_eq!(BitFlag::<u128>::all().raw(), u128::MAX);
+
+        assert!(BitFlag::<u128>::all().is_full());
+        assert_eq!(BitFlag::<u128>::all().len(), 128);
+    }
+
+    #[test]
+    fn test_bit() {
+        assert_eq!(BitFlag::<u8>::bit(7).raw(), 0b1000_0000);
+        assert_eq!(BitFlag::<u8>::bit(0).raw(), 0b1);
+        assert_eq!(BitFlag::<u128>::bit(127).raw(), 1 << 127);
+
+        // Overflowing positions result in an empty flag
+        assert!(BitFlag::<u8>::bit(8).is_empty());
+        assert!(BitFlag::<u32>::bit(200).is_empty());
+    }
+
+    #[test]
+    fn test_count_zeros() {
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        assert_eq!(bf8.count_zeros(), 8);
+        bf8.set(3, true);
+        bf8.set(5, true);
+        assert_eq!(bf8.count_ones(), 2);
+        assert_eq!(bf8.count_zeros(), 6);
+
+        let mut bf128: BitFlag<u128> = BitFlag::new();
+        assert_eq!(bf128.count_zeros(), 128);
+        bf128.fill(true);
+        assert_eq!(bf128.count_ones(), 128);
+        assert_eq!(bf128.count_zeros(), 0);
+    }
+
+    #[test]
+    fn test_len() {
+        // Compare against counting every bit on its own
+        fn count_loop<T: BitflagAble>(bf: &BitFlag<T>) -> usize {
+            bf.iter().filter(|i| *i).count()
+        }
+
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for _ in 0..1000 {
+            // xorshift
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+
+            let bf8 = BitFlag::new_with_value(seed as u8);
+            assert_eq!(bf8.len(), count_loop(&bf8));
+
+            let bf64 = BitFlag::new_with_value(seed);
+            assert_eq!(bf64.len(), count_loop(&bf64));
+
+            let bf128 = BitFlag::new_with_value((seed as u128) << 64 | seed.rotate_left(7) as u128);
+            assert_eq!(bf128.len(), count_loop(&bf128));
+        }
+
+        assert_eq!(BitFlag::<u128>::new().len(), 0);
+        assert_eq!(BitFlag::<i32>::all().len(), 32);
+    }
+
+    #[test]
+    fn test_first_set() {
+        let bf8: BitFlag<u8> = BitFlag::new();
+        assert_eq!(bf8.first_set(), None);
+        assert_eq!(bf8.trailing_zeros(), 8);
+
+        let bf8: BitFlag<u8> = BitFlag::bit(0);
+        assert_eq!(bf8.first_set(), Some(0));
+        assert_eq!(bf8.trailing_zeros(), 0);
+
+        let bf8: BitFlag<u8> = BitFlag::bit(7);
+        assert_eq!(bf8.first_set(), Some(7));
+
+        let bf128: BitFlag<u128> = BitFlag::new();
+        assert_eq!(bf128.first_set(), None);
+        assert_eq!(bf128.trailing_zeros(), 128);
+
+        let bf128: BitFlag<u128> = BitFlag::bit(0);
+        assert_eq!(bf128.first_set(), Some(0));
+
+        let bf128: BitFlag<u128> = BitFlag::bit(127);
+        assert_eq!(bf128.first_set(), Some(127));
+        assert_eq!(bf128.trailing_zeros(), 127);
+    }
+
+    #[test]
+    fn test_last_set() {
+        let bf8: BitFlag<u8> = BitFlag::new();
+        assert_eq!(bf8.last_set(), None);
+        assert_eq!(bf8.leading_zeros(), 8);
+
+        let bf8: BitFlag<u8> = BitFlag::new_with_value(0b1000_0000);
+        assert_eq!(bf8.last_set(), Some(7));
+        assert_eq!(bf8.leading_zeros(), 0);
+
+        let bf8: BitFlag<u8> = BitFlag::new_with_value(0b0001_0110);
+        assert_eq!(bf8.last_set(), Some(4));
+        assert_eq!(bf8.leading_zeros(), 3);
+
+        let bf8: BitFlag<u8> = BitFlag::bit(0);
+        assert_eq!(bf8.last_set(), Some(0));
+
+        let bf128: BitFlag<u128> = BitFlag::bit(127);
+        assert_eq!(bf128.last_set(), Some(127));
+    }
+
+    #[test]
+    fn test_first_unset() {
+        assert_eq!(BitFlag::<u8>::new().first_unset(), Some(0));
+        assert_eq!(BitFlag::<u8>::all().first_unset(), None);
+        assert_eq!(
+            BitFlag::<u8>::new_with_value(0b0101_0101).first_unset(),
+            Some(1)
+        );
+        assert_eq!(
+            BitFlag::<u8>::new_with_value(0b1010_1010).first_unset(),
+            Some(0)
+        );
+        assert_eq!(
+            BitFlag::<u8>::new_with_value(0b0111_1111).first_unset(),
+            Some(7)
+        );
+
+        assert_eq!(BitFlag::<u64>::new().first_unset(), Some(0));
+        assert_eq!(BitFlag::<u64>::all().first_unset(), None);
+        let alternating = 0x5555_5555_5555_5555_u64;
+        assert_eq!(BitFlag::new_with_value(alternating).first_unset(), Some(1));
+        assert_eq!(BitFlag::new_with_value(!alternating).first_unset(), Some(0));
+        assert_eq!(
+            BitFlag::new_with_value(u64::MAX >> 1).first_unset(),
+            Some(63)
+        );
+    }
+
+    #[test]
+    fn test_next_set_after() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b0100_1001);
+        assert_eq!(bf.next_set_after(0), Some(3));
+        assert_eq!(bf.next_set_after(3), Some(6));
+        assert_eq!(bf.next_set_after(4), Some(6));
+        // Last set bit
+        assert_eq!(bf.next_set_after(6), None);
+        assert_eq!(bf.next_set_after(7), None);
+        assert_eq!(bf.next_set_after(8), None);
+
+        assert_eq!(bf.next_unset_after(0), Some(1));
+        assert_eq!(bf.next_unset_after(2), Some(4));
+        assert_eq!(bf.next_unset_after(6), Some(7));
+        assert_eq!(bf.next_unset_after(7), None);
+        assert_eq!(BitFlag::<u8>::all().next_unset_after(0), None);
+
+        let bf: BitFlag<u128> = BitFlag::bit(127);
+        assert_eq!(bf.next_set_after(0), Some(127));
+        assert_eq!(bf.next_set_after(126), Some(127));
+        assert_eq!(bf.next_set_after(127), None);
+    }
+
+    #[test]
+    fn test_nth_set() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1010_0110);
+        assert_eq!(bf.nth_set(0), Some(1));
+        assert_eq!(bf.nth_set(1), Some(2));
+        assert_eq!(bf.nth_set(2), Some(5));
+        assert_eq!(bf.nth_set(bf.len() - 1), Some(7));
+        assert_eq!(bf.nth_set(bf.len()), None);
+        assert_eq!(BitFlag::<u8>::new().nth_set(0), None);
+
+        let mut bf: BitFlag<u128> = BitFlag::new();
+        bf.set(3, true);
+        bf.set(64, true);
+        bf.set(65, true);
+        bf.set(127, true);
+        assert_eq!(bf.nth_set(0), Some(3));
+        assert_eq!(bf.nth_set(1), Some(64));
+        assert_eq!(bf.nth_set(2), Some(65));
+        assert_eq!(bf.nth_set(bf.len() - 1), Some(127));
+        assert_eq!(bf.nth_set(bf.len()), None);
+        assert_eq!(bf.nth_set(1000), None);
+
+        let bf: BitFlag<i32> = BitFlag::all();
+        assert_eq!(bf.nth_set(31), Some(31));
+    }
+
+    #[test]
+    fn test_rank() {
+        let bf: BitFlag<u64> = BitFlag::new_with_value(0b1011_0110);
+        assert_eq!(bf.rank(0), 0);
+        assert_eq!(bf.rank(1), 0);
+        assert_eq!(bf.rank(2), 1);
+        assert_eq!(bf.rank(3), 2);
+        assert_eq!(bf.rank(8), 5);
+        assert_eq!(bf.rank(64), 5);
+        assert_eq!(bf.rank(200), 5);
+
+        let bf: BitFlag<u64> = BitFlag::all();
+        assert_eq!(bf.rank(0), 0);
+        assert_eq!(bf.rank(63), 63);
+        assert_eq!(bf.rank(64), 64);
+
+        // rank and nth_set are inverse to each other
+        let bf: BitFlag<u128> = BitFlag::new_with_value(0xF0F0_0000_0000_0000_1234_5678_9ABC_DEF0);
+        for n in 0..bf.len() {
+            let pos = bf.nth_set(n).unwrap();
+            assert_eq!(bf.rank(pos as u128), n);
+        }
+    }
+
+    #[test]
+    fn test_iter_ones() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1010_0110);
+        assert_eq!(bf.iter_ones().collect::<Vec<_>>(), vec![1, 2, 5, 7]);
+
+        assert_eq!(BitFlag::<u32>::new().iter_ones().count(), 0);
+        assert_eq!(
+            BitFlag::<u32>::all().iter_ones().collect::<Vec<_>>(),
+            (0..32).collect::<Vec<_>>()
+        );
+
+        let mut bf: BitFlag<u128> = BitFlag::new();
+        bf.set(0, true);
+        bf.set(77, true);
+        bf.set(127, true);
+        assert_eq!(bf.iter_ones().collect::<Vec<_>>(), vec![0, 77, 127]);
+    }
+
+    #[test]
+    fn test_iter_zeros() {
+        assert_eq!(BitFlag::<u8>::all().iter_zeros().count(), 0);
+        assert_eq!(
+            BitFlag::<u8>::new().iter_zeros().collect::<Vec<_>>(),
+            (0..8).collect::<Vec<_>>()
+        );
+
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b1010_0110);
+        let zeros: Vec<_> = bf.iter_zeros().collect();
+        assert_eq!(zeros, vec![0, 3, 4, 6]);
+
+        for pos in zeros {
+            bf.set(pos as u8, true);
+        }
+        assert!(bf.is_full());
+
+        let bf: BitFlag<u128> = BitFlag::new_with_value(!(1 << 100));
+        assert_eq!(bf.iter_zeros().collect::<Vec<_>>(), vec![100]);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let bf: BitFlag<u8> = BitFlag::from_fn(|i| i % 2 == 0);
+        assert_eq!(bf.raw(), 0b0101_0101);
+
+        let table = [Some(1), None, None, Some(4)];
+        let bf: BitFlag<u8> = BitFlag::from_fn(|i| table.get(i).is_some_and(|i| i.is_some()));
+        assert_eq!(bf.raw(), 0b1001);
+
+        let mut calls = 0;
+        let bf: BitFlag<u128> = BitFlag::from_fn(|_| {
+            calls += 1;
+            true
+        });
+        assert_eq!(calls, 128);
+        assert!(bf.is_full());
+
+        let mut seed: u128 = 0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C834;
+        for _ in 0..100 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+
+            let bf = BitFlag::new_with_value(seed);
+            let copy: BitFlag<u128> = BitFlag::from_fn(|i| bf.get_unchecked(i as u128));
+            assert_eq!(copy.raw(), bf.raw());
+        }
+    }
+
+    #[test]
+    fn test_set_positions() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_positions(&[0, 3, 3, 7, 8, 200]);
+        assert_eq!(bf.raw(), 0b1000_1001);
+
+        bf.clear_positions(&[3, 3, 9]);
+        assert_eq!(bf.raw(), 0b1000_0001);
+
+        bf.clear_positions(&[]);
+        assert_eq!(bf.raw(), 0b1000_0001);
+
+        let mut bf: BitFlag<u128> = BitFlag::all();
+        bf.clear_positions(&[0, 127, 128]);
+        assert_eq!(bf.raw(), u128::MAX >> 1 & !1);
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut bf: BitFlag<u8> = BitFlag::bit(3);
+        assert!(bf.replace(3, false));
+        assert!(!bf.get(3));
+        assert!(!bf.replace(3, false));
+        assert!(bf.is_empty());
+
+        assert!(!bf.replace(5, true));
+        assert!(bf.replace(5, true));
+        assert_eq!(bf.raw(), 0b10_0000);
+
+        // Overflowing positions are ignored
+        assert!(!bf.replace(8, true));
+        assert_eq!(bf.raw(), 0b10_0000);
+    }
+
+    #[test]
+    fn test_test_and_set() {
+        let mut seen: BitFlag<u32> = BitFlag::new();
+        let items = [3, 7, 3, 31, 7, 0, 31, 3];
+
+        let visited: Vec<_> = items
+            .iter()
+            .filter(|pos| !seen.test_and_set(**pos))
+            .copied()
+            .collect();
+        assert_eq!(visited, vec![3, 7, 31, 0]);
+        assert_eq!(seen.len(), 4);
+
+        assert!(seen.test_and_clear(7));
+        assert!(!seen.test_and_clear(7));
+        assert!(!seen.get(7));
+
+        // Overflowing positions are ignored
+        assert!(!seen.test_and_set(32));
+        assert!(!seen.test_and_clear(32));
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0b1010_0101);
+        for i in 0..16 {
+            bf.update(i, |b| b);
+        }
+        assert_eq!(bf.raw(), 0b1010_0101);
+
+        assert!(bf.update(1, |b| !b));
+        assert!(!bf.update(0, |b| !b));
+        assert_eq!(bf.raw(), 0b1010_0110);
+
+        let threshold = 3;
+        assert!(bf.update(15, |b| b || threshold > 2));
+        assert!(bf.get(15));
+
+        let mut called = false;
+        assert!(!bf.update(16, |_| {
+            called = true;
+            true
+        }));
+        assert!(!called);
+    }
+
+    #[test]
+    fn test_clear_range() {
+        let mut bf: BitFlag<u32> = BitFlag::all();
+        bf.clear_range((0, 31));
+        assert!(bf.is_empty());
+
+        let mut bf: BitFlag<u32> = BitFlag::all();
+        bf.clear_range((4, 11));
+        assert_eq!(bf.raw(), 0xFFFF_F00F);
+
+        let mut bf: BitFlag<u8> = BitFlag::all();
+        bf.clear_range((7, 7));
+        assert_eq!(bf.raw(), 0b0111_1111);
+
+        // Invalid ranges are ignored
+        bf.clear_range((3, 2));
+        bf.clear_range((0, 8));
+        assert_eq!(bf.raw(), 0b0111_1111);
+
+        let mut bf: BitFlag<i64> = BitFlag::all();
+        bf.clear_range((60, 63));
+        assert_eq!(bf.raw(), 0x0FFF_FFFF_FFFF_FFFF);
+    }
+
+    #[test]
+    fn test_toggle_range() {
+        let mut bf: BitFlag<u64> = BitFlag::new_with_value(0xDEAD_BEEF);
+        bf.toggle_range((8, 15));
+        assert_eq!(bf.raw(), 0xDEAD_41EF);
+        bf.toggle_range((8, 15));
+        assert_eq!(bf.raw(), 0xDEAD_BEEF);
+
+        bf.toggle_range((0, 63));
+        assert_eq!(bf.raw(), !0xDEAD_BEEF);
+
+        // Invalid ranges are ignored
+        bf.toggle_range((9, 8));
+        bf.toggle_range((0, 64));
+        assert_eq!(bf.raw(), !0xDEAD_BEEF);
+    }
+
+    #[test]
+    fn test_fill_range() {
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        bf.fill_range((0, 15), true);
+        assert!(bf.is_full());
+        bf.fill_range((0, 15), false);
+        assert!(bf.is_empty());
+
+        bf.fill_range((9, 9), true);
+        assert_eq!(bf.raw(), 1 << 9);
+        bf.fill_range((3, 10), true);
+        assert_eq!(bf.raw(), 0b0111_1111_1000);
+        bf.fill_range((4, 5), false);
+        assert_eq!(bf.raw(), 0b0111_1100_1000);
+
+        // Invalid ranges are ignored
+        bf.fill_range((10, 3), true);
+        bf.fill_range((10, 3), false);
+        assert_eq!(bf.raw(), 0b0111_1100_1000);
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(BitFlag::<u8>::mask((0, 7)).raw(), u8::MAX);
+        assert_eq!(BitFlag::<u8>::mask((2, 4)).raw(), 0b1_1100);
+        assert_eq!(BitFlag::<u8>::mask((7, 7)).raw(), 0b1000_0000);
+        assert_eq!(BitFlag::<u128>::mask((0, 127)).raw(), u128::MAX);
+        assert_eq!(BitFlag::<u128>::mask((64, 127)).raw(), u128::MAX << 64);
+
+        // Invalid ranges
+        assert!(BitFlag::<u8>::mask((4, 2)).is_empty());
+        assert!(BitFlag::<u8>::mask((0, 8)).is_empty());
+
+        assert!(BitFlag::<u8>::low_mask(0).is_empty());
+        assert_eq!(BitFlag::<u8>::low_mask(3).raw(), 0b111);
+        assert_eq!(BitFlag::<u8>::low_mask(8).raw(), u8::MAX);
+        assert_eq!(BitFlag::<u128>::low_mask(127).raw(), u128::MAX >> 1);
+        assert_eq!(BitFlag::<u128>::low_mask(128).raw(), u128::MAX);
+    }
+
+    #[test]
+    fn test_keep_only() {
+        let orig: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+
+        let mut bf = orig;
+        assert!(bf.keep_only((8, 19)));
+        assert_eq!(bf.raw(), 0x000D_BE00);
+        assert_eq!(bf.raw() >> 8, orig.get_range((8, 19)).unwrap());
+
+        let mut bf = orig;
+        assert!(bf.keep_only((0, 31)));
+        assert_eq!(bf.raw(), orig.raw());
+
+        // Invalid ranges don't change anything
+        let mut bf = orig;
+        assert!(!bf.keep_only((20, 19)));
+        assert!(!bf.keep_only((0, 32)));
+        assert_eq!(bf.raw(), orig.raw());
+    }
+
+    #[test]
+    fn test_take_range() {
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b101110);
+        let expected = bf.get_range((1, 3));
+        assert_eq!(bf.take_range((1, 3)), expected);
+        assert_eq!(bf.get_range((1, 3)), Some(0));
+        assert_eq!(bf.raw(), 0b100000);
+
+        assert_eq!(bf.take_range((3, 0)), None);
+        assert_eq!(bf.take_range((0, 8)), None);
+        assert_eq!(bf.raw(), 0b100000);
+
+        let mut bf: BitFlag<i16> = BitFlag::all();
+        let expected = bf.get_range((12, 15));
+        assert_eq!(bf.take_range((12, 15)), expected);
+        assert_eq!(expected, Some(0b1111));
+        assert_eq!(bf.raw(), 0x0FFF);
+
+        let mut bf: BitFlag<u128> = BitFlag::all();
+        assert_eq!(bf.take_range((0, 127)), Some(u128::MAX));
+        assert!(bf.is_empty());
+    }
+
+    #[test]
+    fn test_copy_range() {
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0b1011);
+        assert!(bf.copy_range((0, 3), 8));
+        assert_eq!(bf.raw(), 0b1011_0000_1011);
+
+        // Overlapping forward copy
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0b1_0110);
+        assert!(bf.copy_range((1, 4), 2));
+        assert_eq!(bf.raw(), 0b10_1110);
+
+        // Overlapping backward copy
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0b1_0110_0000);
+        assert!(bf.copy_range((5, 8), 3));
+        assert_eq!(bf.raw(), 0b1_0101_1000);
+
+        // Destination doesn't fit
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b1111);
+        assert!(!bf.copy_range((0, 3), 5));
+        assert!(!bf.copy_range((3, 2), 0));
+        assert_eq!(bf.raw(), 0b1111);
+        assert!(bf.copy_range((0, 3), 4));
+        assert!(bf.is_full());
+    }
+
+    #[test]
+    fn test_copy_from() {
+        let other: BitFlag<u32> = BitFlag::all();
+        let mut bf: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+        bf.copy_from(&other, (8, 15));
+        assert_eq!(bf.raw(), 0xDEAD_FFEF);
+
+        let other: BitFlag<u32> = BitFlag::new();
+        bf.copy_from(&other, (28, 31));
+        assert_eq!(bf.raw(), 0x0EAD_FFEF);
+
+        // Invalid ranges are ignored
+        bf.copy_from(&other, (0, 32));
+        bf.copy_from(&other, (4, 3));
+        assert_eq!(bf.raw(), 0x0EAD_FFEF);
+    }
+
+    #[test]
+    fn test_swap_bits() {
+        let mut bf: BitFlag<u32> = BitFlag::new_with_value(0b1001);
+        bf.swap_bits(0, 3);
+        assert_eq!(bf.raw(), 0b1001);
+        bf.swap_bits(1, 2);
+        assert_eq!(bf.raw(), 0b1001);
+
+        bf.swap_bits(0, 31);
+        assert_eq!(bf.raw(), 1 << 31 | 0b1000);
+        bf.swap_bits(3, 3);
+        assert_eq!(bf.raw(), 1 << 31 | 0b1000);
+
+        // Overflowing positions don't change anything
+        bf.swap_bits(3, 32);
+        bf.swap_bits(32, 3);
+        assert_eq!(bf.raw(), 1 << 31 | 0b1000);
+    }
+
+    #[test]
+    fn test_swap_ranges() {
+        let mut bf: BitFlag<u64> = BitFlag::new_with_value(0xAB_CD);
+        assert!(bf.swap_ranges((0, 7), (8, 15)));
+        assert_eq!(bf.raw(), 0xCD_AB);
+        assert!(bf.swap_ranges((8, 15), (56, 63)));
+        assert_eq!(bf.raw(), 0xCD00_0000_0000_00AB);
+
+        // Adjacent single bits
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b01);
+        assert!(bf.swap_ranges((1, 1), (0, 0)));
+        assert_eq!(bf.raw(), 0b10);
+
+        // Rejected calls don't change anything
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b1100_1010);
+        assert!(!bf.swap_ranges((0, 3), (4, 6)));
+        assert!(!bf.swap_ranges((0, 3), (3, 6)));
+        assert!(!bf.swap_ranges((2, 5), (0, 3)));
+        assert!(!bf.swap_ranges((3, 0), (4, 7)));
+        assert!(!bf.swap_ranges((0, 3), (5, 8)));
+        assert_eq!(bf.raw(), 0b1100_1010);
+    }
+
+    #[test]
+    fn test_reverse_bits() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b0000_0001);
+        assert_eq!(bf.reverse_bits().raw(), 0b1000_0000);
+
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1101_0010);
+        assert_eq!(bf.reverse_bits().raw(), 0b0100_1011);
+        assert_eq!(bf.reverse_bits().reverse_bits().raw(), bf.raw());
+
+        let mut bf: BitFlag<u128> = BitFlag::new_with_value(0b11);
+        bf.reverse_bits_in_place();
+        assert_eq!(bf.raw(), 0b11 << 126);
+        bf.reverse_bits_in_place();
+        assert_eq!(bf.raw(), 0b11);
+    }
+
+    #[test]
+    fn test_reverse_range() {
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b1000_0110);
+        bf.reverse_range((1, 3));
+        assert_eq!(bf.raw(), 0b1000_1100);
+
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0b1111_0000_0110_1111);
+        bf.reverse_range((4, 8));
+        assert_eq!(bf.raw(), 0b1111_0000_1100_1111);
+        bf.reverse_range((4, 8));
+        assert_eq!(bf.raw(), 0b1111_0000_0110_1111);
+
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b0000_0001);
+        bf.reverse_range((0, 7));
+        assert_eq!(bf.raw(), 0b1000_0000);
+        bf.reverse_range((7, 7));
+        assert_eq!(bf.raw(), 0b1000_0000);
+
+        let mut bf: BitFlag<i32> = BitFlag::new_with_value(i32::MIN);
+        bf.reverse_range((27, 31));
+        assert_eq!(bf.raw(), 1 << 27);
+
+        // Invalid ranges are ignored
+        bf.reverse_range((3, 1));
+        bf.reverse_range((0, 32));
+        assert_eq!(bf.raw(), 1 << 27);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let bf: BitFlag<u64> = BitFlag::new_with_value(0xDEAD_BEEF);
+        assert_eq!(bf.rotate_left(64), bf);
+        assert_eq!(bf.rotate_right(64), bf);
+        assert_eq!(bf.rotate_left(68), bf.rotate_left(4));
+        assert_eq!(bf.rotate_left(5).rotate_right(5), bf);
+
+        let bf: BitFlag<u64> = BitFlag::bit(63);
+        assert_eq!(bf.rotate_left(1).raw(), 1);
+        assert_eq!(BitFlag::<u64>::bit(0).rotate_right(1).raw(), 1 << 63);
+
+        let mut bf: BitFlag<u8> = BitFlag::new_with_value(0b1000_0001);
+        bf.rotate_left_in_place(1);
+        assert_eq!(bf.raw(), 0b0000_0011);
+        bf.rotate_right_in_place(2);
+        assert_eq!(bf.raw(), 0b1100_0000);
+        bf.rotate_left_in_place(8);
+        assert_eq!(bf.raw(), 0b1100_0000);
+    }
+
+    #[test]
+    fn test_shift() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+        assert_eq!((bf << 0).raw(), bf.raw());
+        assert_eq!((bf >> 0).raw(), bf.raw());
+        assert_eq!((bf << 4).raw(), 0xEAD_BEEF0);
+        assert_eq!((bf >> 4).raw(), 0xDEA_DBEE);
+        assert!((bf << 32).is_empty());
+        assert!((bf >> 32).is_empty());
+        assert!((bf << 1000).is_empty());
+        assert!((bf >> u32::MAX).is_empty());
+
+        // Signed types are filled with zeros too
+        let bf: BitFlag<i16> = BitFlag::new_with_value(i16::MIN);
+        assert_eq!((bf >> 15).raw(), 1);
+
+        let mut bf: BitFlag<u128> = BitFlag::bit(0);
+        bf <<= 127;
+        assert_eq!(bf.raw(), 1 << 127);
+        bf >>= 127;
+        assert_eq!(bf.raw(), 1);
+        bf <<= 128;
+        assert!(bf.is_empty());
+    }
+
+    #[test]
+    fn test_swap_bytes() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0x1234_5678);
+        assert_eq!(bf.swap_bytes().raw(), 0x7856_3412);
+        assert_eq!(bf.swap_bytes().swap_bytes().raw(), bf.raw());
+
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1010_0001);
+        assert_eq!(bf.swap_bytes().raw(), bf.raw());
+        assert_eq!(bf.to_be().raw(), bf.raw());
+        assert_eq!(bf.to_le().raw(), bf.raw());
+
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0x1234_5678);
+        assert_eq!(bf.to_be().raw(), 0x1234_5678_u32.to_be());
+        assert_eq!(bf.to_le().raw(), 0x1234_5678_u32.to_le());
+    }
+
+    #[test]
+    fn test_get_byte() {
+        let bf: BitFlag<u64> = BitFlag::new_with_value(0x0123_4567_89AB_CDEF);
+        let bytes: Vec<_> = (0..8).map(|i| bf.get_byte(i).unwrap()).collect();
+        assert_eq!(bytes, vec![0xEF, 0xCD, 0xAB, 0x89, 0x67, 0x45, 0x23, 0x01]);
+        assert_eq!(bf.get_byte(8), None);
+
+        let bf: BitFlag<i16> = BitFlag::new_with_value(i16::MIN);
+        assert_eq!(bf.get_byte(1), Some(0x80));
+    }
+
+    #[test]
+    fn test_set_byte() {
+        for i in 0..8 {
+            let mut bf: BitFlag<u64> = BitFlag::new_with_value(0x0123_4567_89AB_CDEF);
+            let orig = bf.raw();
+            assert!(bf.set_byte(i, 0x5A));
+            assert_eq!(bf.get_byte(i), Some(0x5A));
+
+            // Neighboring bytes are untouched
+            let mask = 0xFF << (i * 8);
+            assert_eq!(bf.raw() & !mask, orig & !mask);
+        }
+
+        let mut bf: BitFlag<u64> = BitFlag::new();
+        assert!(!bf.set_byte(8, 0xFF));
+        assert!(bf.is_empty());
+    }
+
+    #[test]
+    fn test_bit_ops() {
+        let values: [u16; 5] = [0, 0xFFFF, 0b1010_0101, 0xF0F0, 0x1234];
+
+        for a in values.iter().map(|v| BitFlag::new_with_value(*v)) {
+            for b in values.iter().map(|v| BitFlag::new_with_value(*v)) {
+                assert_eq!(((a | b) & !b).raw(), (a & !b).raw());
+                assert_eq!((a ^ b).raw(), ((a | b) & !(a & b)).raw());
+                assert_eq!((a | b).raw(), a.raw() | b.raw());
+                assert_eq!((a & b).raw(), a.raw() & b.raw());
+                assert_eq!((a ^ b).raw(), a.raw() ^ b.raw());
+
+                let mut c = a;
+                c |= b;
+                assert_eq!(c.raw(), (a | b).raw());
+                let mut c = a;
+                c &= b;
+                assert_eq!(c.raw(), (a & b).raw());
+                let mut c = a;
+                c ^= b;
+                assert_eq!(c.raw(), (a ^ b).raw());
+            }
+
+            assert_eq!((!a).raw(), !a.raw());
+            assert_eq!((!!a).raw(), a.raw());
+            assert!((a | !a).is_full());
+        }
+    }
+
+    #[test]
+    fn test_bit_ops_raw() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1010);
+
+        assert_eq!((bf | 0b0100u8).raw(), 0b1110);
+        assert_eq!((bf & 0b0010).raw(), 0b0010);
+        assert_eq!((bf ^ 0b1111).raw(), 0b0101);
+
+        let mask: u8 = 0b1100_0011;
+        assert_eq!((bf | mask).raw(), (bf | BitFlag::from(mask)).raw());
+        assert_eq!((bf & mask).raw(), (bf & BitFlag::from(mask)).raw());
+        assert_eq!((bf ^ mask).raw(), (bf ^ BitFlag::from(mask)).raw());
+
+        let mut bf: BitFlag<u32> = BitFlag::new();
+        bf |= 0xFF;
+        bf &= 0x0F;
+        bf ^= 0x3;
+        assert_eq!(bf.raw(), 0b1100);
+    }
+
+    #[test]
+    fn test_sub() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b0110);
+
+        // Subtracting a superset
+        assert!((bf - BitFlag::new_with_value(0b1111)).is_empty());
+        assert!((bf - 0b0110).is_empty());
+
+        // Subtracting disjoint bits
+        assert_eq!((bf - BitFlag::new_with_value(0b1001)).raw(), 0b0110);
+        assert_eq!((bf - 0b1000_0000).raw(), 0b0110);
+
+        // No arithmetic borrow
+        assert_eq!((bf - 0b0011).raw(), 0b0100);
+
+        const WRITE_MASK: u8 = 0b0010;
+        let mut perms = bf;
+        perms -= WRITE_MASK;
+        assert_eq!(perms.raw(), 0b0100);
+        perms -= BitFlag::all();
+        assert!(perms.is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1011);
+
+        assert!(bf.contains_all(0b0011));
+        assert!(bf.contains_all(BitFlag::new_with_value(0b1010)));
+        assert!(!bf.contains_all(0b0111));
+        assert!(bf.contains_any(0b0111));
+        assert!(!bf.contains_any(0b0100));
+
+        // Empty rhs
+        assert!(bf.contains_all(0));
+        assert!(!bf.contains_any(0));
+        assert!(BitFlag::<u8>::new().contains_all(BitFlag::new()));
+        assert!(!BitFlag::<u8>::new().contains_any(BitFlag::new()));
+    }
+
+    #[test]
+    fn test_set_relations() {
+        let a: BitFlag<u8> = BitFlag::new_with_value(0b0011);
+        let b: BitFlag<u8> = BitFlag::new_with_value(0b0111);
+        let c: BitFlag<u8> = BitFlag::new_with_value(0b1000);
+        let empty: BitFlag<u8> = BitFlag::new();
+        let full: BitFlag<u8> = BitFlag::all();
+
+        assert!(a.is_subset(&a) && a.is_superset(&a));
+        assert!(a.is_subset(&b) && !b.is_subset(&a));
+        assert!(b.is_superset(&a) && !a.is_superset(&b));
+        assert!(a.is_disjoint(&c) && !a.is_disjoint(&b));
+
+        assert!(empty.is_subset(&a) && empty.is_subset(&empty));
+        assert!(empty.is_disjoint(&empty) && empty.is_disjoint(&full));
+        assert!(full.is_superset(&b) && !full.is_disjoint(&full));
+
+        let a: BitFlag<u64> = BitFlag::new_with_value(1 << 63 | 1);
+        let full: BitFlag<u64> = BitFlag::all();
+        let empty: BitFlag<u64> = BitFlag::new();
+        assert!(a.is_subset(&full) && full.is_superset(&a));
+        assert!(empty.is_subset(&a) && a.is_disjoint(&empty));
+        assert!(full.is_subset(&full) && !full.is_subset(&a));
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        assert!(bf.insert(4));
+        assert!(!bf.insert(4));
+        assert!(bf.contains(4));
+
+        assert!(bf.remove(4));
+        assert!(!bf.remove(4));
+        assert!(!bf.contains(4));
+
+        let added = [1, 2, 2, 3, 1, 15]
+            .iter()
+            .filter(|i| bf.insert(**i))
+            .count();
+        assert_eq!(added, 4);
+
+        // Overflowing positions are ignored
+        assert!(!bf.insert(16));
+        assert!(!bf.remove(16));
+        assert!(!bf.contains(16));
+        assert_eq!(bf.raw(), 1 << 15 | 0b1110);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut bf: BitFlag<u64> = BitFlag::new_with_value(1 << 40 | 0b1011_0110);
+        let expected_calls = bf.len();
+
+        let mut calls = vec![];
+        bf.retain(|pos| {
+            calls.push(pos);
+            pos % 2 == 0
+        });
+        assert_eq!(calls.len(), expected_calls);
+        assert_eq!(calls, vec![1, 2, 4, 5, 7, 40]);
+        assert_eq!(bf.raw(), 1 << 40 | 0b0001_0100);
+
+        // Unset bits can't be changed
+        bf.retain(|_| true);
+        assert_eq!(bf.raw(), 1 << 40 | 0b0001_0100);
+
+        bf.retain(|_| false);
+        assert!(bf.is_empty());
+    }
+
+    #[test]
+    fn test_intersect_all() {
+        let masks: Vec<BitFlag<u64>> = vec![
+            BitFlag::new_with_value(0b0111),
+            BitFlag::new_with_value(0b1110),
+            BitFlag::new_with_value(0b0110),
+        ];
+        assert_eq!(BitFlag::intersect_all(masks.clone()).raw(), 0b0110);
+        assert_eq!(BitFlag::union_all(masks).raw(), 0b1111);
+
+        // Empty iterators
+        assert!(BitFlag::<u64>::intersect_all(vec![]).is_full());
+        assert!(BitFlag::<u64>::union_all(vec![]).is_empty());
+
+        // Stops as soon as the intersection is empty
+        let masks: Vec<BitFlag<u64>> = vec![
+            BitFlag::new_with_value(0b01),
+            BitFlag::new_with_value(0b10),
+            BitFlag::new_with_value(0b11),
+        ];
+        let mut consumed = 0;
+        let res = BitFlag::intersect_all(masks.into_iter().inspect(|_| consumed += 1));
+        assert!(res.is_empty());
+        assert_eq!(consumed, 2);
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let a: BitFlag<u8> = BitFlag::new_with_value(0b1011_0010);
+        let b: BitFlag<u8> = BitFlag::new_with_value(0b1001_0111);
+        assert_eq!(a.hamming_distance(&a), 0);
+        assert_eq!(a.hamming_distance(&b), 3);
+        assert_eq!(b.hamming_distance(&a), 3);
+        assert_eq!(a.hamming_distance(&!a), 8);
+
+        let a: BitFlag<u128> = BitFlag::new_with_value(0xDEAD_BEEF << 64);
+        assert_eq!(a.hamming_distance(&a), 0);
+        assert_eq!(a.hamming_distance(&!a), 128);
+        assert_eq!(a.hamming_distance(&BitFlag::new()), a.len());
+    }
+
+    #[test]
+    fn test_diff() {
+        let old: BitFlag<u64> = BitFlag::new_with_value(1 << 63 | 0b0110);
+        assert_eq!(old.diff(&old).count(), 0);
+
+        let new: BitFlag<u64> = BitFlag::new_with_value(1 << 40 | 0b0011);
+        assert_eq!(
+            old.diff(&new).collect::<Vec<_>>(),
+            vec![(0, true), (2, false), (40, true), (63, false)]
+        );
+    }
+
+    #[test]
+    fn test_overlap_count() {
+        let a: BitFlag<u32> = BitFlag::new_with_value(0b1111_0000);
+        let b: BitFlag<u32> = BitFlag::new_with_value(0b0000_1111);
+        let c: BitFlag<u32> = BitFlag::new_with_value(0b0011_1100);
+        let empty: BitFlag<u32> = BitFlag::new();
+
+        assert_eq!(a.overlap_count(&b), 0);
+        assert_eq!(a.overlap_count(&a), 4);
+        assert_eq!(a.overlap_count(&c), 2);
+        assert_eq!(c.overlap_count(&b), 2);
+        assert_eq!(a.overlap_count(&empty), 0);
+        assert_eq!(empty.overlap_count(&empty), 0);
+    }
+
+    #[test]
+    fn test_assign_masked() {
+        let other: BitFlag<u16> = BitFlag::new_with_value(0xABCD);
+
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0x1234);
+        bf.assign_masked(&other, &BitFlag::all());
+        assert_eq!(bf.raw(), 0xABCD);
+
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0x1234);
+        bf.assign_masked(&other, &BitFlag::new());
+        assert_eq!(bf.raw(), 0x1234);
+
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        bf.assign_masked(&BitFlag::all(), &BitFlag::new_with_value(0x5555));
+        assert_eq!(bf.raw(), 0x5555);
+        bf.assign_masked(&BitFlag::new(), &BitFlag::new_with_value(0x00FF));
+        assert_eq!(bf.raw(), 0x5500);
+    }
+
+    #[test]
+    fn test_extract_deposit() {
+        let bf: BitFlag<u16> = BitFlag::new_with_value(0b1011_0110_0101_1001);
+        let mask: BitFlag<u16> = BitFlag::new_with_value(0b1111_0000_0000_1001);
+        assert_eq!(bf.extract(&mask), 0b10_1111);
+
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0b0000_0110);
+        bf.deposit(&mask, 0b10_1001);
+        assert_eq!(bf.raw(), 0b1010_0000_0000_0111);
+
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for _ in 0..200 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+
+            let mask: BitFlag<u64> = BitFlag::new_with_value(seed);
+            let value = seed.rotate_left(17) & BitFlag::<u64>::low_mask(mask.len() as u64).raw();
+
+            let mut bf: BitFlag<u64> = BitFlag::new_with_value(seed.rotate_left(31));
+            bf.deposit(&mask, value);
+            assert_eq!(bf.extract(&mask), value);
         }
     }
 
     #[test]
-    fn toggle_all_u32() {
-        let mut bf8: BitFlag<u32> = BitFlag::new();
-        for i in 0..31 {
-            bf8.set(i, true);
-            assert_eq!(bf8.get(i), true);
+    fn test_complement() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+        assert_eq!(bf.complement().raw(), 0x2152_4110);
+        assert_eq!(bf.complement().complement(), bf);
+        assert_eq!(bf.complement(), !bf);
+        assert!((bf | bf.complement()).is_full());
+        assert!((bf & bf.complement()).is_empty());
 
-            bf8.set(i, false);
-            assert_eq!(bf8.get(i), false);
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.complement_in_place();
+        assert!(bf.is_full());
+        bf.complement_in_place();
+        assert!(bf.is_empty());
+    }
+
+    #[test]
+    fn test_any_none_all_set() {
+        fn check<T: BitflagAble>(values: &[T]) {
+            for bf in values.iter().map(|v| BitFlag::new_with_value(*v)) {
+                assert_eq!(bf.any(), bf.count_ones() > 0);
+                assert_eq!(bf.none(), bf.count_ones() == 0);
+                assert_eq!(bf.all_set(), bf.len() == BitFlag::<T>::size());
+            }
         }
-        assert!(bf8.is_empty());
+
+        check::<u8>(&[0, 1, 0x80, u8::MAX]);
+        check::<u16>(&[0, 1, 0x8000, u16::MAX]);
+        check::<u32>(&[0, 1, u32::MAX - 1, u32::MAX]);
+        check::<u64>(&[0, 1, u64::MAX >> 1, u64::MAX]);
+        check::<u128>(&[0, 1, u128::MAX << 1, u128::MAX]);
+        check::<i32>(&[0, -1, i32::MIN, i32::MAX]);
+
+        assert!(BitFlag::<u128>::all().all_set());
+        assert!(BitFlag::<u128>::new().none());
+        assert!(BitFlag::<u128>::bit(127).any());
     }
 
     #[test]
-    fn test_set_range() {
-        let mut bf: BitFlag<u32> = BitFlag::new();
-        let max = u32::MAX;
+    fn test_range_predicates() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0x0001_F000);
+        assert!(bf.all_set_in((12, 16)));
+        assert!(bf.any_set_in((12, 16)));
+        assert!(!bf.all_set_in((12, 17)));
+        assert!(bf.any_set_in((16, 23)));
+        assert!(!bf.any_set_in((17, 31)));
 
-        bf.set_range((3, 6), max);
-        assert_eq!(bf.raw(), 0b01111000);
+        // Single bit ranges
+        assert!(bf.all_set_in((15, 15)));
+        assert!(bf.any_set_in((15, 15)));
+        assert!(!bf.all_set_in((0, 0)));
+        assert!(!bf.any_set_in((0, 0)));
 
-        bf.clear();
+        // Invalid ranges
+        let bf: BitFlag<u32> = BitFlag::all();
+        assert!(!bf.all_set_in((3, 2)));
+        assert!(!bf.any_set_in((0, 32)));
+    }
 
-        bf.set_range((28, 31), max);
-        assert_eq!(bf.raw(), 0b11110000000000000000000000000000);
+    #[test]
+    fn test_first_difference() {
+        let a: BitFlag<u64> = BitFlag::new_with_value(0xDEAD_BEEF);
+        assert_eq!(a.first_difference(&a), None);
 
-        bf.clear();
+        let b: BitFlag<u64> = BitFlag::new_with_value(0xDEAD_BEEF | 1 << 63);
+        assert_eq!(a.first_difference(&b), Some(63));
+        assert_eq!(b.first_difference(&a), Some(63));
 
-        let e: u32 = 0b1011;
-        let e: BitFlag<u32> = BitFlag::new_with_value(e);
-        bf.set_range((28, 31), e);
-        assert_eq!(bf.raw(), 0b10110000000000000000000000000000);
-        assert!(!bf.is_empty());
+        let c: BitFlag<u64> = BitFlag::new_with_value(0xDEAD_BEE0);
+        assert_eq!(a.first_difference(&c), Some(0));
+
+        let a: BitFlag<u8> = BitFlag::new();
+        assert_eq!(a.first_difference(&BitFlag::bit(7)), Some(7));
     }
 
     #[test]
-    fn test_get_range() {
-        let bf: BitFlag<u8> = BitFlag::new_with_value(0b101110);
-        assert_eq!(bf.get_range((3, 0)), None);
+    fn test_parity() {
+        assert!(!BitFlag::<u8>::new().parity());
+        assert!(BitFlag::<u8>::bit(5).parity());
+        assert!(!BitFlag::<u8>::new_with_value(0b11).parity());
 
-        assert_eq!(bf.get_range((1, 2)), Some(0b11));
+        // u8 has an even width, so the complement keeps the parity
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b0111);
+        assert!(bf.parity());
+        assert_eq!(bf.complement().parity(), bf.parity());
 
-        assert_eq!(bf.get_range((0, 3)), Some(0b1110));
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1011_0001);
+        assert!(bf.parity_in_range((0, 3)));
+        assert!(bf.parity_in_range((4, 7)));
+        assert!(!bf.parity_in_range((0, 7)));
+        assert!(!bf.parity_in_range((1, 3)));
+        assert!(!bf.parity_in_range((3, 1)));
+    }
 
-        assert_eq!(bf.get_range((0, 4)), Some(0b01110));
+    #[test]
+    fn test_eq() {
+        let a: BitFlag<u32> = BitFlag::new_with_value(0b1010);
+        let mut b: BitFlag<u32> = BitFlag::new();
+        b.set(1, true);
+        b.set(3, true);
+        assert_eq!(a, b);
 
-        assert_eq!(bf.get_range((4, 5)), Some(0b10));
+        b.toggle(0);
+        assert_ne!(a, b);
+    }
 
-        assert_eq!(bf.get_range((0, 5)), Some(0b101110));
+    #[test]
+    fn test_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+        use std::hash::{Hash, Hasher};
+
+        fn hash<H: Hash>(h: &H) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            h.hash(&mut hasher);
+            hasher.finish()
+        }
+        assert_eq!(
+            hash(&BitFlag::from(5u32)),
+            hash(&BitFlag::new_with_value(5u32))
+        );
+
+        let mut set: HashSet<BitFlag<u32>> = HashSet::new();
+        assert!(set.insert(BitFlag::from(5)));
+        assert!(!set.insert(BitFlag::new_with_value(5)));
+        assert!(set.insert(BitFlag::bit(2)));
+        assert!(set.contains(&BitFlag::new_with_value(0b101)));
+        assert!(set.contains(&BitFlag::new_with_value(0b100)));
+        assert!(!set.contains(&BitFlag::new()));
+    }
+
+    #[test]
+    fn test_ord() {
+        use std::collections::BTreeMap;
+
+        let mut flags: Vec<BitFlag<u8>> = vec![0b100, 0b1, 0b11, 0]
+            .into_iter()
+            .map(BitFlag::new_with_value)
+            .collect();
+        flags.sort();
+        assert_eq!(
+            flags.iter().map(|f| f.raw()).collect::<Vec<_>>(),
+            vec![0, 0b1, 0b11, 0b100]
+        );
+
+        // Numeric, not subset based
+        assert!(BitFlag::<u8>::new_with_value(0b100) > BitFlag::new_with_value(0b011));
+
+        let mut map = BTreeMap::new();
+        map.insert(BitFlag::<u8>::bit(3), "b");
+        map.insert(BitFlag::<u8>::bit(1), "a");
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(map.get(&BitFlag::new_with_value(0b1000)), Some(&"b"));
+    }
+
+    #[test]
+    fn test_raw_cmp() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1010);
+        assert_eq!(bf, 0b1010u8);
+        assert_eq!(0b1010u8, bf);
+        assert_ne!(bf, 0b1011);
+        assert_ne!(0b1011, bf);
+
+        assert!(bf > 0b1001);
+        assert!(bf < 0b1011);
+        assert!(bf >= 0b1010);
+        assert!(0b1001 < bf);
+        assert!(0b1011 > bf);
+
+        let bf: BitFlag<u128> = BitFlag::bit(127);
+        assert_eq!(bf, 1 << 127);
+        assert!(bf > u128::MAX >> 1);
+    }
+
+    #[test]
+    fn test_radix_fmt() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0xBEEF);
+
+        assert_eq!(format!("{:b}", bf), "1011111011101111");
+        assert_eq!(format!("{:x}", bf), "beef");
+        assert_eq!(format!("{:X}", bf), "BEEF");
+        assert_eq!(format!("{:o}", bf), "137357");
+
+        assert_eq!(format!("{:#b}", bf), "0b1011111011101111");
+        assert_eq!(format!("{:#x}", bf), "0xbeef");
+        assert_eq!(format!("{:#X}", bf), "0xBEEF");
+        assert_eq!(format!("{:#o}", bf), "0o137357");
+
+        assert_eq!(format!("{:08x}", bf), "0000beef");
+        assert_eq!(format!("{:#010X}", bf), "0x0000BEEF");
+        assert_eq!(
+            format!("{:>6o}", BitFlag::<u8>::new_with_value(8)),
+            "    10"
+        );
+    }
+
+    #[test]
+    fn test_padded_display() {
+        let bf: BitFlag<u8> = BitFlag::bit(1);
+        assert_eq!(bf.to_string(), "10");
+        assert_eq!(format!("{:#}", bf), "00000010");
+        assert_eq!(bf.to_padded_string(), "00000010");
+
+        let bf: BitFlag<u16> = BitFlag::new_with_value(0b101);
+        assert_eq!(bf.to_padded_string(), "0000000000000101");
+        assert_eq!(BitFlag::<u16>::new().to_padded_string(), "0".repeat(16));
+        assert_eq!(BitFlag::<u16>::all().to_padded_string(), "1".repeat(16));
+    }
+
+    #[test]
+    fn test_debug() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b101);
+        assert_eq!(format!("{:?}", bf), "101");
+
+        assert_eq!(
+            format!("{:#?}", BitFlag::<u8>::new()),
+            "BitFlag { bits: 8, set: [] }"
+        );
+
+        let bf: BitFlag<u64> = BitFlag::new_with_value(1 << 40 | 1 << 17 | 1 << 3);
+        assert_eq!(
+            format!("{:#?}", bf),
+            "BitFlag { bits: 64, set: [3, 17, 40] }"
+        );
+    }
+
+    #[test]
+    fn test_display_grouped() {
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b1011_0110);
+        assert_eq!(bf.display_grouped(4).to_string(), "1011_0110");
+        assert_eq!(bf.display_grouped(3).to_string(), "10_110_110");
+        assert_eq!(bf.display_grouped(8).to_string(), "10110110");
+        assert_eq!(bf.display_grouped(0).to_string(), "10110110");
+        assert_eq!(BitFlag::<u8>::new().display_grouped(4).to_string(), "0");
+
+        let bf: BitFlag<u16> = BitFlag::new_with_value(0b1011_1000_1111_0000);
+        assert_eq!(bf.display_grouped(4).to_string(), "1011_1000_1111_0000");
+        let bf: BitFlag<u16> = BitFlag::new_with_value(0b10_1111_0000);
+        assert_eq!(bf.display_grouped(4).to_string(), "10_1111_0000");
+
+        let bf: BitFlag<u128> = BitFlag::new_with_value(1 << 127 | 0xFF);
+        let expected = format!("10000000{}_11111111", "_00000000".repeat(14));
+        assert_eq!(bf.display_grouped(8).to_string(), expected);
+        let bf: BitFlag<u128> = BitFlag::new_with_value(1 << 127 | 1 << 124);
+        let expected = format!("1001{}", "_0000".repeat(31));
+        assert_eq!(bf.display_grouped(4).to_string(), expected);
+    }
+
+    #[test]
+    fn test_layout() {
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(size_of::<BitFlag<u8>>(), size_of::<u8>());
+        assert_eq!(size_of::<BitFlag<u64>>(), size_of::<u64>());
+        assert_eq!(size_of::<BitFlag<u128>>(), size_of::<u128>());
+        assert_eq!(align_of::<BitFlag<u64>>(), align_of::<u64>());
+        assert_eq!(align_of::<BitFlag<u128>>(), align_of::<u128>());
+    }
+
+    #[test]
+    fn test_raw_mut() {
+        let mut bf: BitFlag<u64> = BitFlag::new();
+        *bf.raw_mut() = 0b101;
+        assert_eq!(bf, 0b101);
+
+        fn write_through(ptr: *mut u64) {
+            unsafe { *ptr |= 1 << 63 };
+        }
+        write_through(bf.raw_mut());
+        assert_eq!(bf, 1 << 63 | 0b101);
+    }
+
+    #[test]
+    fn test_as_ref() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(42);
+        let raw: &u32 = bf.as_ref();
+        assert_eq!(*raw, 42);
+
+        fn takes_ref<R: AsRef<u32>>(r: R) -> u32 {
+            *r.as_ref()
+        }
+        assert_eq!(takes_ref(bf), 42);
+    }
+
+    #[test]
+    fn test_borrow_lookup() {
+        use std::collections::{HashMap, HashSet};
+
+        let mut map: HashMap<u64, &str> = HashMap::new();
+        map.insert(0b1010, "ten");
+        map.insert(1 << 40, "big");
+
+        let key: BitFlag<u64> = BitFlag::new_with_value(0b1010);
+        assert_eq!(map.get(Borrow::<u64>::borrow(&key)), Some(&"ten"));
+        assert_eq!(map.get(BitFlag::<u64>::bit(40).as_ref()), Some(&"big"));
+
+        // The other way around: a set of bitflags can be queried with raw values
+        let set: HashSet<BitFlag<u64>> = vec![key, BitFlag::bit(3)].into_iter().collect();
+        assert!(set.contains(&0b1010u64));
+        assert!(set.contains(&8u64));
+        assert!(!set.contains(&1u64));
+    }
+
+    #[test]
+    fn test_into_inner() {
+        macro_rules! round_trip {
+            ($($t:ty),*) => {
+                $(
+                    let x = <$t>::MAX - 1;
+                    assert_eq!(BitFlag::from(x).into_inner(), x);
+                    let back: $t = BitFlag::from(x).into();
+                    assert_eq!(back, x);
+                )*
+            };
+        }
+
+        round_trip!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+    }
+
+    #[test]
+    fn test_with_bit_range() {
+        let bf: BitFlag<u8> = BitFlag::new()
+            .with_bit(0, true)
+            .with_range((4, 7), 0b1010u8);
+        assert_eq!(bf, 0b1010_0001u8);
+
+        let bf: BitFlag<u16> = BitFlag::all()
+            .with_bit(15, false)
+            .with_range((0, 3), 0u16)
+            .with_bit(1, true);
+        assert_eq!(bf, 0b0111_1111_1111_0010u16);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_with_bit_range_overflow() {
+        // Overflows are ignored like in set and set_range
+        let bf: BitFlag<u8> = BitFlag::new()
+            .with_bit(8, true)
+            .with_range((6, 9), 0b1111u8)
+            .with_range((3, 2), 1u8);
+        assert!(bf.is_empty());
+    }
+
+    #[test]
+    fn test_index() {
+        let bf: BitFlag<u16> = BitFlag::new_with_value(0b1000_0000_0000_1001);
+        assert!(bf[0]);
+        assert!(!bf[1]);
+        assert!(bf[3]);
+        assert!(bf[15]);
+
+        let bf: BitFlag<u128> = BitFlag::bit(127);
+        assert!(bf[127]);
+        assert!(!bf[126]);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit index 8 out of range for a bitflag of 8 bits")]
+    fn test_index_out_of_range() {
+        let bf: BitFlag<u8> = BitFlag::all();
+        let _ = bf[8];
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    #[should_panic(expected = "position 8 is out of range for a bitflag of 8 bits")]
+    fn test_strict_set() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set(8, true);
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    #[should_panic(expected = "position 64 is out of range for a bitflag of 64 bits")]
+    fn test_strict_get() {
+        let bf: BitFlag<u64> = BitFlag::all();
+        bf.get(64);
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    #[should_panic(expected = "invalid range (4, 16) for a bitflag of 16 bits")]
+    fn test_strict_set_range() {
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        bf.set_range((4, 16), 1u16);
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    #[should_panic(expected = "invalid range (3, 2) for a bitflag of 8 bits")]
+    fn test_strict_reversed_range() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_range((3, 2), 1u8);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_silent_overflow() {
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        bf.set(16, true);
+        bf.set_range((4, 16), 1u16);
+        bf.set_range((3, 2), 1u16);
+        assert!(bf.is_empty());
+        assert!(!BitFlag::<u16>::all().get(16));
+    }
+
+    #[test]
+    fn test_try_set() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        assert_eq!(bf.try_set(7, true), Ok(()));
+        assert_eq!(bf.try_set(0, true), Ok(()));
+        assert_eq!(bf.try_set(7, false), Ok(()));
+        assert_eq!(bf, 1u8);
+
+        let mut bf: BitFlag<u32> = BitFlag::new_with_value(0xF0F0);
+        assert_eq!(
+            bf.try_set(32, true),
+            Err(BitFlagError::PositionOutOfRange { pos: 32, width: 32 })
+        );
+        assert_eq!(
+            bf.try_set(200, false),
+            Err(BitFlagError::PositionOutOfRange {
+                pos: 200,
+                width: 32
+            })
+        );
+        assert_eq!(bf, 0xF0F0u32);
+    }
+
+    #[test]
+    fn test_try_get() {
+        let bf: BitFlag<u32> = BitFlag::bit(31);
+        assert_eq!(bf.try_get(31), Some(true));
+        assert_eq!(bf.try_get(30), Some(false));
+        assert_eq!(bf.try_get(32), None);
+
+        let bf: BitFlag<u128> = BitFlag::all();
+        assert_eq!(bf.try_get(BitFlag::<u128>::size() as u128 - 1), Some(true));
+        assert_eq!(bf.try_get(BitFlag::<u128>::size() as u128), None);
+
+        // Same as check_overflow
+        let mut bf8: BitFlag<u8> = BitFlag::new();
+        assert!(bf8.try_set(8, true).is_err());
+        assert_eq!(bf8.try_get(8), None);
+        assert_eq!(bf8.try_get(7), Some(false));
+    }
+
+    #[test]
+    fn test_try_set_range() {
+        let mut bf: BitFlag<u16> = BitFlag::new_with_value(0b1000_0000_0000_0001);
+        assert_eq!(bf.try_set_range((3, 6), 0b1111u16), Ok(()));
+        assert_eq!(bf, 0b1000_0000_0111_1001u16);
+        assert_eq!(bf.try_set_range((12, 15), 0u16), Ok(()));
+        assert_eq!(bf, 0b0000_0000_0111_1001u16);
+
+        // One bit too wide for the range, nothing gets written
+        assert_eq!(
+            bf.try_set_range((3, 6), 0b1_0000u16),
+            Err(BitFlagError::ValueTooWide {
+                required_bits: 5,
+                available: 4
+            })
+        );
+        assert_eq!(bf, 0b0000_0000_0111_1001u16);
+
+        assert_eq!(
+            bf.try_set_range((6, 3), 1u16),
+            Err(BitFlagError::InvalidRange { start: 6, end: 3 })
+        );
+        assert_eq!(
+            bf.try_set_range((10, 16), 1u16),
+            Err(BitFlagError::PositionOutOfRange { pos: 16, width: 16 })
+        );
+        assert_eq!(bf, 0b0000_0000_0111_1001u16);
+
+        // The full width works
+        let mut bf: BitFlag<u64> = BitFlag::new();
+        assert_eq!(bf.try_set_range((0, 63), u64::MAX), Ok(()));
+        assert!(bf.is_full());
+    }
+
+    #[test]
+    fn test_try_get_range() {
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+        assert_eq!(bf.try_get_range((0, 15)), Ok(0xBEEF));
+        assert_eq!(bf.try_get_range((16, 31)), Ok(0xDEAD));
+        assert_eq!(bf.try_get_range((4, 11)).ok(), bf.get_range((4, 11)));
+        assert_eq!(bf.try_get_range((0, 31)), Ok(0xDEAD_BEEF));
+
+        assert_eq!(
+            bf.try_get_range((9, 8)),
+            Err(BitFlagError::InvalidRange { start: 9, end: 8 })
+        );
+        assert_eq!(
+            bf.try_get_range((30, 32)),
+            Err(BitFlagError::PositionOutOfRange { pos: 32, width: 32 })
+        );
+        // Reversed ranges are reported first
+        assert_eq!(
+            bf.try_get_range((40, 35)),
+            Err(BitFlagError::InvalidRange { start: 40, end: 35 })
+        );
+    }
+
+    #[test]
+    fn test_range_clamped() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        assert_eq!(bf.set_range_clamped((5, 8), 0b1111u8), 3);
+        assert_eq!(bf, 0b1110_0000u8);
+        assert_eq!(bf.get_range_clamped((5, 8)), (0b111, 3));
+        assert_eq!(bf.get_range_clamped((4, 7)), (0b1110, 4));
+
+        // Reversed and fully overflowing ranges do nothing
+        assert_eq!(bf.set_range_clamped((3, 1), 0b11u8), 0);
+        assert_eq!(bf.set_range_clamped((8, 9), 0b11u8), 0);
+        assert_eq!(bf.get_range_clamped((3, 1)), (0, 0));
+        assert_eq!(bf.get_range_clamped((9, 12)), (0, 0));
+        assert_eq!(bf, 0b1110_0000u8);
+
+        let mut bf: BitFlag<u128> = BitFlag::new();
+        assert_eq!(bf.set_range_clamped((120, 128), u128::MAX), 8);
+        assert_eq!(bf.raw(), 0xFF << 120);
+        assert_eq!(bf.get_range_clamped((120, 128)), (0xFF, 8));
+        assert_eq!(bf.get_range_clamped((0, 255)), (0xFF << 120, 128));
+    }
+
+    #[test]
+    fn test_usize_positions() {
+        let mut bf: BitFlag<u128> = BitFlag::new();
+        bf.set_at(100, true);
+        bf.set_at(128, true);
+        bf.toggle_at(3);
+        assert_eq!(bf.raw(), 1 << 100 | 0b1000);
+        assert!(bf.get_at(100));
+        assert!(!bf.get_at(101));
+        assert!(!bf.get_at(usize::MAX));
+
+        let mut bf: BitFlag<i64> = BitFlag::new();
+        bf.set_at(63, true);
+        bf.toggle_at(64);
+        assert_eq!(bf, i64::MIN);
+        assert!(bf.get_at(63));
+
+        let mut bf: BitFlag<u8> = BitFlag::all();
+        bf.set_at(0, false);
+        bf.toggle_at(7);
+        bf.set_at(1000, false);
+        assert_eq!(bf, 0b0111_1110u8);
+        assert!(!bf.get_at(0));
+        assert!(!bf.get_at(8));
+    }
+
+    #[test]
+    fn test_range_bounds() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_bits(0..8, 0xFFu8);
+        assert!(bf.is_full());
+
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_bits(.., 0xA5u8);
+        assert_eq!(bf, 0xA5u8);
+        assert_eq!(bf.get_bits(..), Some(0xA5));
+        assert_eq!(bf.get_bits(0..8), Some(0xA5));
+        assert_eq!(bf.get_bits(0..=7), Some(0xA5));
+
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        bf.set_bits(4..8, 0b1111u16);
+        assert_eq!(bf, 0b1111_0000u16);
+        bf.set_bits(8..=9, 0b11u16);
+        assert_eq!(bf, 0b11_1111_0000u16);
+        bf.set_bits(..2, 0b11u16);
+        assert_eq!(bf, 0b11_1111_0011u16);
+        bf.set_bits(14.., 0b11u16);
+        assert_eq!(bf, 0b1100_0011_1111_0011u16);
+        bf.set_bits(..=0, 0u16);
+        assert_eq!(bf, 0b1100_0011_1111_0010u16);
+
+        assert_eq!(bf.get_bits(4..8), Some(0b1111));
+        assert_eq!(bf.get_bits(14..), Some(0b11));
+        assert_eq!(bf.get_bits(..2), Some(0b10));
+        assert_eq!(bf.get_bits(12..=15), Some(0b1100));
+
+        // Empty and overflowing ranges
+        assert_eq!(bf.get_bits(3..3), None);
+        assert_eq!(bf.get_bits(..0), None);
+        assert_eq!(bf.get_bits(10..17), None);
+        bf.set_bits(3..3, 1u16);
+        #[cfg(not(feature = "strict"))]
+        bf.set_bits(10..=16, 1u16);
+        assert_eq!(bf, 0b1100_0011_1111_0010u16);
+
+        let bf: BitFlag<u128> = BitFlag::all();
+        assert_eq!(bf.get_bits(..), Some(u128::MAX));
+        assert_eq!(bf.get_bits(127..), Some(1));
+    }
+
+    #[test]
+    fn test_usize_ranges() {
+        let mut bf: BitFlag<u128> = BitFlag::new();
+        bf.set_range_at((0, 127), u128::MAX - 1);
+        assert_eq!(bf.raw(), u128::MAX - 1);
+        assert_eq!(bf.get_range_at((0, 127)), Some(u128::MAX - 1));
+        assert_eq!(bf.get_range_at((64, 127)), Some(u64::MAX as u128));
+        assert_eq!(bf.get_range((0, 127)), bf.get_range_at((0, 127)));
+
+        bf.set_range_at((120, 127), 0u128);
+        assert_eq!(bf.raw(), (u128::MAX >> 8) - 1);
+        bf.set_range((120, 127), 0xFFu128);
+        assert_eq!(bf.raw(), u128::MAX - 1);
+
+        // Indices beyond the u8 range are simply out of range
+        assert_eq!(bf.get_range_at((0, 300)), None);
+        assert_eq!(bf.get_range_at((256, 260)), None);
+        assert_eq!(
+            bf.try_get_range((0, 128)),
+            Err(BitFlagError::PositionOutOfRange {
+                pos: 128,
+                width: 128
+            })
+        );
+
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_range_at((2, 5), 0b1001u8);
+        assert_eq!(bf, 0b10_0100u8);
+        assert_eq!(bf.get_range_at((2, 5)), Some(0b1001));
+        assert_eq!(bf.get_range_at((5, 2)), None);
+    }
+
+    #[test]
+    fn test_checked_add() {
+        let a: BitFlag<u8> = BitFlag::new_with_value(0b0011);
+        let b: BitFlag<u8> = BitFlag::new_with_value(0b0110);
+
+        // Common bits carry with `+` but not with the union
+        assert_eq!(a + b, 0b1001u8);
+        assert_eq!(a | b, 0b0111u8);
+        assert_ne!(a + b, a | b);
+        assert_eq!(a.checked_add(b), Some(a + b));
+
+        let max: BitFlag<u8> = BitFlag::all();
+        assert_eq!(max.checked_add(BitFlag::bit(0)), None);
+        assert_eq!(max.checked_add(BitFlag::new()), Some(max));
+
+        // Signed types overflow at the top bit of the unsigned value
+        let bf: BitFlag<i16> = BitFlag::new_with_value(i16::MAX);
+        assert_eq!(bf.checked_add(BitFlag::bit(0)), Some(BitFlag::bit(15)));
+        assert_eq!(BitFlag::<i16>::all().checked_add(BitFlag::bit(0)), None);
+    }
+
+    #[test]
+    fn test_wrapping_saturating_add() {
+        let max: BitFlag<u8> = BitFlag::new_with_value(0xFF);
+        let one: BitFlag<u8> = BitFlag::bit(0);
+        assert_eq!(max.wrapping_add(one), 0u8);
+        assert_eq!(max.saturating_add(one), 0xFFu8);
+        assert_eq!(one.wrapping_add(one), 2u8);
+        assert_eq!(one.saturating_add(one), 2u8);
+
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0xF0);
+        assert_eq!(bf.wrapping_add(BitFlag::new_with_value(0x20)), 0x10u8);
+        assert_eq!(bf.saturating_add(BitFlag::new_with_value(0x20)), 0xFFu8);
+
+        // Signed types saturate to all bits set
+        let bf: BitFlag<i32> = BitFlag::all();
+        assert!(bf.saturating_add(BitFlag::bit(0)).is_full());
+        assert!(bf.wrapping_add(BitFlag::bit(0)).is_empty());
+    }
+
+    #[test]
+    fn test_get_range_as() {
+        let hdr: BitFlag<u64> = BitFlag::new_with_value(0xDEAD_BEEF_CAFE_0042);
+
+        let flags: u8 = hdr.get_range_as((0, 7)).unwrap();
+        assert_eq!(flags, 0x42);
+        assert_eq!(hdr.get_range_as::<u8>((16, 23)), Some(0xFE));
+        assert_eq!(hdr.get_range_as::<u8>((60, 63)), Some(0xD));
+        assert_eq!(hdr.get_range_as::<u16>((48, 63)), Some(0xDEAD));
+        assert_eq!(hdr.get_range_as::<u16>((4, 11)), Some(0x04));
+        assert_eq!(hdr.get_range_as::<i16>((48, 63)), Some(0xDEADu16 as i16));
+        assert_eq!(
+            hdr.get_range_as::<u128>((0, 63)),
+            Some(0xDEAD_BEEF_CAFE_0042)
+        );
+
+        // Ranges wider than the target type are rejected
+        assert_eq!(hdr.get_range_as::<u8>((0, 8)), None);
+        assert_eq!(hdr.get_range_as::<u16>((40, 63)), None);
+        // Invalid ranges as well
+        assert_eq!(hdr.get_range_as::<u8>((7, 0)), None);
+        assert_eq!(hdr.get_range_as::<u8>((60, 64)), None);
+    }
+
+    #[test]
+    fn test_try_set_range_from() {
+        let version: u8 = 0x2A;
+        let port: u16 = 0xBEEF;
+
+        let mut hdr: BitFlag<u64> = BitFlag::new();
+        assert_eq!(hdr.try_set_range_from((8, 15), version), Ok(()));
+        assert_eq!(hdr.try_set_range_from((16, 31), port), Ok(()));
+        assert_eq!(hdr, 0xBEEF_2A00u64);
+        assert_eq!(hdr.get_range_as::<u8>((8, 15)), Some(version));
+
+        let mut hdr: BitFlag<u128> = BitFlag::new();
+        assert_eq!(hdr.try_set_range_from((120, 127), version), Ok(()));
+        assert_eq!(hdr.try_set_range_from((0, 15), port), Ok(()));
+        assert_eq!(hdr.raw(), 0x2A << 120 | 0xBEEF);
+
+        // The set bits of the value have to fit into the range
+        assert_eq!(
+            hdr.try_set_range_from((120, 127), 0x1FFu16),
+            Err(BitFlagError::ValueTooWide {
+                required_bits: 9,
+                available: 8
+            })
+        );
+        assert_eq!(hdr.try_set_range_from((124, 127), 0x0Fu8), Ok(()));
+        assert_eq!(hdr.raw(), 0xFA << 120 | 0xBEEF);
+
+        // Values wider than T
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        assert_eq!(
+            bf.try_set_range_from((0, 7), 0x100u16),
+            Err(BitFlagError::ValueTooWide {
+                required_bits: 9,
+                available: 8
+            })
+        );
+        assert_eq!(
+            bf.try_set_range_from((4, 7), 0x100u16),
+            Err(BitFlagError::ValueTooWide {
+                required_bits: 9,
+                available: 4
+            })
+        );
+
+        // Invalid ranges are reported before too wide values
+        assert_eq!(
+            bf.try_set_range_from((9, 3), 0x1FFu16),
+            Err(BitFlagError::InvalidRange { start: 9, end: 3 })
+        );
+        assert_eq!(
+            bf.try_set_range_from((3, 9), 0x1FFu16),
+            Err(BitFlagError::PositionOutOfRange { pos: 9, width: 8 })
+        );
+        assert_eq!(bf.try_set_range_from((0, 7), 0xFFu128), Ok(()));
+        assert!(bf.is_full());
+    }
+
+    #[test]
+    fn test_u128_full_width_ranges() {
+        let full = (0, 127);
+
+        let mut bf: BitFlag<u128> = BitFlag::new();
+        bf.set_range_at(full, u128::MAX);
+        assert!(bf.is_full());
+        assert_eq!(bf.get_range_at(full), Some(u128::MAX));
+        assert_eq!(bf.try_get_range_at(full), Ok(u128::MAX));
+        assert_eq!(bf.get_range_clamped_at((0, 1000)), (u128::MAX, 128));
+        assert!(bf.all_set_in_at(full));
+        assert!(!bf.parity_in_range_at(full));
+
+        bf.clear_range_at(full);
+        assert!(bf.is_empty());
+        assert!(!bf.any_set_in_at(full));
+        bf.fill_range_at(full, true);
+        assert_eq!(bf, BitFlag::<u128>::mask_at(full));
+        bf.toggle_range_at(full);
+        assert!(bf.is_empty());
+
+        assert_eq!(bf.try_set_range_at(full, 1u128 << 127 | 1), Ok(()));
+        bf.reverse_range_at(full);
+        assert_eq!(bf.raw(), 1 << 127 | 1);
+        assert!(bf.keep_only_at(full));
+        assert_eq!(bf.take_range_at(full), Some(1 << 127 | 1));
+        assert!(bf.is_empty());
+
+        assert_eq!(bf.try_set_range_from_at((120, 127), 0xABu8), Ok(()));
+        assert_eq!(bf.get_range_as_at::<u8>((120, 127)), Some(0xAB));
+        assert_eq!(bf.set_range_clamped_at((124, 300), 0u128), 4);
+        assert_eq!(bf.raw(), 0xB << 120);
+
+        let other: BitFlag<u128> = BitFlag::all();
+        bf.copy_from_at(&other, full);
+        assert!(bf.is_full());
+        bf = BitFlag::new_with_value(0xFF);
+        assert!(bf.copy_range_at((0, 7), 120));
+        assert!(bf.swap_ranges_at((0, 63), (64, 127)));
+        assert_eq!(bf.raw(), 0xFF << 64 | 0xFF << 56);
+
+        let bf = BitFlag::<u128>::new().with_range_at(full, u128::MAX);
+        assert!(bf.is_full());
+        assert_eq!(bf.get_range_at_unchecked(full), u128::MAX);
+
+        // Indices beyond the u8 range
+        assert_eq!(bf.get_range_at((0, 256)), None);
+        assert_eq!(bf.get_bits(0..=300), None);
+        assert_eq!(bf.get_bits(300..), None);
+        let mut copy = bf;
+        assert!(!copy.copy_range_at((0, 7), 1000));
+        assert!(!copy.copy_range_at((0, 7), 121));
+        assert_eq!(copy, bf);
+        assert!(BitFlag::<u128>::mask_at((200, 300)).is_empty());
     }
 }
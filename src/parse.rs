@@ -0,0 +1,396 @@
+use crate::{bitflagable::BitflagAble, BitFlag};
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Error returned when parsing a bitflag from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBitFlagError {
+    /// The input doesn't contain any digits
+    EmptyInput,
+    /// The character at `position` (counted in chars, including any prefix) isn't a valid digit
+    InvalidDigit { position: usize },
+    /// The value doesn't fit into a bitflag with `width` bits
+    Overflow { width: usize },
+    /// The input has an odd amount of hex digits
+    OddLength,
+}
+
+impl Display for ParseBitFlagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBitFlagError::EmptyInput => write!(f, "cannot parse bitflag from empty input"),
+            ParseBitFlagError::InvalidDigit { position } => {
+                write!(f, "invalid digit at position {}", position)
+            }
+            ParseBitFlagError::Overflow { width } => {
+                write!(f, "value doesn't fit into {} bits", width)
+            }
+            ParseBitFlagError::OddLength => write!(f, "odd amount of hex digits in input"),
+        }
+    }
+}
+
+impl Error for ParseBitFlagError {}
+
+impl<T: BitflagAble> BitFlag<T> {
+    /// Parses a bitflag from a string of `0` and `1` without prefix, most significant bit first.
+    /// Underscores between the digits are ignored. Fails if there are more digits than
+    /// [`size`](Self::size)
+    #[inline]
+    pub fn from_binary_str(s: &str) -> Result<Self, ParseBitFlagError> {
+        let digits = parse_digits(s, 0, 2, true)?;
+        if digits.len() > Self::size() {
+            return Err(Self::overflow());
+        }
+
+        Ok(digits
+            .iter()
+            .rev()
+            .enumerate()
+            .fold(Self::new(), |mut bf, (i, bit)| {
+                bf.set_unchecked(T::from(i as u8), *bit == 1);
+                bf
+            }))
+    }
+
+    /// Parses a bitflag from a string of hex digits with an optional `0x` prefix. Both upper and
+    /// lower case digits are accepted. Fails if the amount of digits is odd or exceeds
+    /// `size() / 4`
+    #[inline]
+    pub fn from_hex_str(s: &str) -> Result<Self, ParseBitFlagError> {
+        let offset = if s.starts_with("0x") || s.starts_with("0X") {
+            2
+        } else {
+            0
+        };
+
+        let digits = parse_digits(&s[offset..], offset, 16, false)?;
+        if digits.len() & 1 == 1 {
+            return Err(ParseBitFlagError::OddLength);
+        }
+
+        if digits.len() > Self::size() / 4 {
+            return Err(Self::overflow());
+        }
+
+        let val = digits.iter().fold(T::from(0u8), |val, nibble| {
+            (val << T::from(4u8)) | T::from(*nibble)
+        });
+        Ok(Self::new_with_value(val))
+    }
+
+    /// Returns the lowercase hex representation with exactly `size() / 4` characters
+    #[inline]
+    pub fn to_hex_string(&self) -> String {
+        format!("{:0width$x}", self.raw(), width = Self::size() / 4)
+    }
+
+    /// Returns the binary representation with exactly [`size`](Self::size) characters, most
+    /// significant bit first
+    #[inline]
+    pub fn to_binary_string(&self) -> String {
+        self.to_padded_string()
+    }
+
+    /// Returns the overflow error for this bitflag
+    #[inline]
+    fn overflow() -> ParseBitFlagError {
+        ParseBitFlagError::Overflow {
+            width: Self::size(),
+        }
+    }
+}
+
+/// Returns the values of all digits in `s`, skipping underscores if `skip_underscores` is set.
+/// `offset` is the amount of chars preceding `s` in the original input and is used to report the
+/// position of invalid digits
+fn parse_digits(
+    s: &str,
+    offset: usize,
+    radix: u32,
+    skip_underscores: bool,
+) -> Result<Vec<u8>, ParseBitFlagError> {
+    let digits = s
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !skip_underscores || *c != '_')
+        .map(|(i, c)| {
+            c.to_digit(radix)
+                .map(|d| d as u8)
+                .ok_or(ParseBitFlagError::InvalidDigit {
+                    position: offset + i,
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if digits.is_empty() {
+        return Err(ParseBitFlagError::EmptyInput);
+    }
+
+    Ok(digits)
+}
+
+/// Parses a bitflag from a binary (`0b`), hexadecimal (`0x`) or decimal string. Underscores
+/// between the digits are ignored.
+impl<T: BitflagAble> FromStr for BitFlag<T> {
+    type Err = ParseBitFlagError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, radix) = match s.get(..2) {
+            Some("0b") | Some("0B") => (2, 2),
+            Some("0x") | Some("0X") => (2, 16),
+            _ => (0, 10),
+        };
+
+        // Validate first so invalid digits can be reported with their position
+        parse_digits(&s[offset..], offset, radix, true)?;
+
+        let digits: String = s[offset..].chars().filter(|c| *c != '_').collect();
+        T::from_str_radix(&digits, radix)
+            .map(BitFlag::new_with_value)
+            .map_err(|_| Self::overflow())
+    }
+}
+
+/// Same as [`FromStr`].
+impl<T: BitflagAble> TryFrom<&str> for BitFlag<T> {
+    type Error = ParseBitFlagError;
+
+    #[inline]
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "0b1010".parse::<BitFlag<u8>>(),
+            Ok(BitFlag::new_with_value(0b1010))
+        );
+        assert_eq!(
+            "0xF0".parse::<BitFlag<u8>>(),
+            Ok(BitFlag::new_with_value(0xF0))
+        );
+        assert_eq!(
+            "0Xf0".parse::<BitFlag<u8>>(),
+            Ok(BitFlag::new_with_value(0xF0))
+        );
+        assert_eq!("12".parse::<BitFlag<u8>>(), Ok(BitFlag::new_with_value(12)));
+        assert_eq!(
+            "0b1111_0000".parse::<BitFlag<u8>>(),
+            Ok(BitFlag::new_with_value(0b1111_0000))
+        );
+        assert_eq!(
+            "0xDEAD_BEEF".parse::<BitFlag<u32>>(),
+            Ok(BitFlag::new_with_value(0xDEAD_BEEF))
+        );
+        assert_eq!(
+            "1_000".parse::<BitFlag<u16>>(),
+            Ok(BitFlag::new_with_value(1000))
+        );
+
+        // Signed types can have all bits set
+        assert_eq!("0xFFFF".parse::<BitFlag<i16>>(), Ok(BitFlag::all()));
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        assert_eq!(
+            "256".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::Overflow { width: 8 })
+        );
+        assert_eq!(
+            "0x100".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::Overflow { width: 8 })
+        );
+        assert_eq!(
+            "0b1_0000_0000".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::Overflow { width: 8 })
+        );
+
+        assert_eq!(
+            "".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::EmptyInput)
+        );
+        assert_eq!(
+            "0x".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::EmptyInput)
+        );
+        assert_eq!(
+            "__".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::EmptyInput)
+        );
+
+        assert_eq!(
+            "abc".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::InvalidDigit { position: 0 })
+        );
+        assert_eq!(
+            "0b102".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::InvalidDigit { position: 4 })
+        );
+        assert_eq!(
+            "0xFG".parse::<BitFlag<u8>>(),
+            Err(ParseBitFlagError::InvalidDigit { position: 3 })
+        );
+        assert_eq!(
+            "-1".parse::<BitFlag<i32>>(),
+            Err(ParseBitFlagError::InvalidDigit { position: 0 })
+        );
+    }
+
+    #[test]
+    fn test_binary_str() {
+        assert_eq!(
+            BitFlag::<u8>::from_binary_str("01101"),
+            Ok(BitFlag::new_with_value(0b01101))
+        );
+        assert_eq!(
+            BitFlag::<u16>::from_binary_str("1111_0000_1010_0101"),
+            Ok(BitFlag::new_with_value(0xF0A5))
+        );
+
+        let bf: BitFlag<u8> = BitFlag::new_with_value(0b101);
+        assert_eq!(bf.to_binary_string(), "00000101");
+        assert_eq!(BitFlag::from_binary_str(&bf.to_binary_string()), Ok(bf));
+
+        let bf: BitFlag<u32> = BitFlag::new_with_value(0xDEAD_BEEF);
+        assert_eq!(bf.to_binary_string().len(), 32);
+        assert_eq!(BitFlag::from_binary_str(&bf.to_binary_string()), Ok(bf));
+
+        let bf: BitFlag<u128> = BitFlag::bit(127);
+        assert_eq!(BitFlag::from_binary_str(&bf.to_binary_string()), Ok(bf));
+    }
+
+    #[test]
+    fn test_binary_str_errors() {
+        assert_eq!(
+            BitFlag::<u8>::from_binary_str("000000001"),
+            Err(ParseBitFlagError::Overflow { width: 8 })
+        );
+        assert_eq!(
+            BitFlag::<u8>::from_binary_str("0000_0001"),
+            Ok(BitFlag::new_with_value(1))
+        );
+        assert_eq!(
+            BitFlag::<u8>::from_binary_str("0b1"),
+            Err(ParseBitFlagError::InvalidDigit { position: 1 })
+        );
+        assert_eq!(
+            BitFlag::<u8>::from_binary_str("012"),
+            Err(ParseBitFlagError::InvalidDigit { position: 2 })
+        );
+        assert_eq!(
+            BitFlag::<u8>::from_binary_str(""),
+            Err(ParseBitFlagError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_hex_str() {
+        assert_eq!(
+            BitFlag::<u32>::from_hex_str("00ff3a00"),
+            Ok(BitFlag::new_with_value(0x00FF_3A00))
+        );
+        assert_eq!(
+            BitFlag::<u32>::from_hex_str("0x00FF3A00"),
+            BitFlag::<u32>::from_hex_str("00ff3a00")
+        );
+        assert_eq!(
+            BitFlag::<u32>::from_hex_str("0XaBcD"),
+            Ok(BitFlag::new_with_value(0xABCD))
+        );
+
+        fn round_trip<T: BitflagAble>(bf: BitFlag<T>) {
+            let hex = bf.to_hex_string();
+            assert_eq!(hex.len(), BitFlag::<T>::size() / 4);
+            assert_eq!(hex, hex.to_lowercase());
+            assert_eq!(BitFlag::from_hex_str(&hex), Ok(bf));
+        }
+        round_trip(BitFlag::<u8>::new_with_value(0x0A));
+        round_trip(BitFlag::<u16>::new_with_value(0xBEEF));
+        round_trip(BitFlag::<u32>::new_with_value(0xDEAD_BEEF));
+        round_trip(BitFlag::<u64>::new_with_value(0x0123_4567_89AB_CDEF));
+        round_trip(BitFlag::<u128>::all());
+        round_trip(BitFlag::<i16>::all());
+
+        assert_eq!(BitFlag::<u8>::new_with_value(0xA).to_hex_string(), "0a");
+    }
+
+    #[test]
+    fn test_hex_str_errors() {
+        assert_eq!(
+            BitFlag::<u32>::from_hex_str("abc"),
+            Err(ParseBitFlagError::OddLength)
+        );
+        assert_eq!(
+            BitFlag::<u32>::from_hex_str("zz"),
+            Err(ParseBitFlagError::InvalidDigit { position: 0 })
+        );
+        assert_eq!(
+            BitFlag::<u32>::from_hex_str("0xaz"),
+            Err(ParseBitFlagError::InvalidDigit { position: 3 })
+        );
+        assert_eq!(
+            BitFlag::<u16>::from_hex_str("ff_00"),
+            Err(ParseBitFlagError::InvalidDigit { position: 2 })
+        );
+        assert_eq!(
+            BitFlag::<u8>::from_hex_str("0100"),
+            Err(ParseBitFlagError::Overflow { width: 8 })
+        );
+        assert_eq!(
+            BitFlag::<u8>::from_hex_str("0x"),
+            Err(ParseBitFlagError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        assert_eq!(
+            BitFlag::<u8>::try_from("0b101"),
+            Ok(BitFlag::new_with_value(0b101))
+        );
+
+        match BitFlag::<u8>::try_from("") {
+            Err(ParseBitFlagError::EmptyInput) => {}
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        match BitFlag::<u8>::try_from("0b1_12") {
+            Err(ParseBitFlagError::InvalidDigit { position }) => assert_eq!(position, 5),
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        match BitFlag::<u16>::try_from("0x1_0000") {
+            Err(ParseBitFlagError::Overflow { width }) => assert_eq!(width, 16),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        assert_eq!(
+            ParseBitFlagError::EmptyInput.to_string(),
+            "cannot parse bitflag from empty input"
+        );
+        assert_eq!(
+            ParseBitFlagError::InvalidDigit { position: 3 }.to_string(),
+            "invalid digit at position 3"
+        );
+        assert_eq!(
+            ParseBitFlagError::Overflow { width: 8 }.to_string(),
+            "value doesn't fit into 8 bits"
+        );
+    }
+}
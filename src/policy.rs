@@ -0,0 +1,178 @@
+use crate::{bitflagable::BitflagAble, BitFlag};
+
+/// Strategy deciding what happens with positions exceeding the width of a bitflag. Used by
+/// [`BitFlag::set_with_policy`] and [`BitFlag::get_with_policy`].
+pub trait OverflowPolicy {
+    /// Maps `pos` to the position that should be accessed or `None` if the access should be
+    /// skipped. Only called for positions that would cause an overflow
+    fn resolve<T: BitflagAble>(pos: T) -> Option<T>;
+}
+
+/// Silently ignores overflowing positions. This is the behavior of [`BitFlag::set`] and
+/// [`BitFlag::get`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ignore;
+
+/// Panics on overflowing positions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Panic;
+
+/// Wraps overflowing positions around modulo the width
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Wrap;
+
+/// Maps overflowing positions to the most significant bit
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Saturate;
+
+impl OverflowPolicy for Ignore {
+    #[inline]
+    fn resolve<T: BitflagAble>(_pos: T) -> Option<T> {
+        None
+    }
+}
+
+impl OverflowPolicy for Panic {
+    #[inline]
+    fn resolve<T: BitflagAble>(pos: T) -> Option<T> {
+        panic!(
+            "position {} is out of range for a bitflag of {} bits",
+            pos,
+            BitFlag::<T>::size()
+        );
+    }
+}
+
+impl OverflowPolicy for Wrap {
+    #[inline]
+    fn resolve<T: BitflagAble>(pos: T) -> Option<T> {
+        // All widths are powers of two
+        Some(pos & T::from((BitFlag::<T>::size() - 1) as u8))
+    }
+}
+
+impl OverflowPolicy for Saturate {
+    #[inline]
+    fn resolve<T: BitflagAble>(_pos: T) -> Option<T> {
+        Some(T::from((BitFlag::<T>::size() - 1) as u8))
+    }
+}
+
+impl<T: BitflagAble> BitFlag<T> {
+    /// Sets a bit at the given `pos` to `val`, handling overflowing positions with `policy`
+    #[inline]
+    pub fn set_with_policy<P: OverflowPolicy>(&mut self, _policy: P, pos: T, val: bool) {
+        if let Some(pos) = Self::resolve_pos::<P>(pos) {
+            self.set_unchecked(pos, val);
+        }
+    }
+
+    /// Gets a bit at the given `pos`, handling overflowing positions with `policy`. Skipped
+    /// positions return `false`
+    #[inline]
+    pub fn get_with_policy<P: OverflowPolicy>(&self, _policy: P, pos: T) -> bool {
+        match Self::resolve_pos::<P>(pos) {
+            Some(pos) => self.get_unchecked(pos),
+            None => false,
+        }
+    }
+
+    /// Sets a bit at the given `pos` modulo [`size`](Self::size) to `val`
+    #[inline]
+    pub fn set_wrapping(&mut self, pos: T, val: bool) {
+        self.set_with_policy(Wrap, pos, val);
+    }
+
+    /// Gets a bit at the given `pos` modulo [`size`](Self::size)
+    #[inline]
+    pub fn get_wrapping(&self, pos: T) -> bool {
+        self.get_with_policy(Wrap, pos)
+    }
+
+    /// Returns the position to access for `pos` under the policy `P`
+    #[inline]
+    fn resolve_pos<P: OverflowPolicy>(pos: T) -> Option<T> {
+        if Self::is_overflow(pos) {
+            return P::resolve(pos);
+        }
+
+        Some(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_with_policy(Ignore, 3, true);
+        bf.set_with_policy(Ignore, 8, true);
+        bf.set_with_policy(Ignore, 200, true);
+        assert_eq!(bf, 0b1000u8);
+        assert!(bf.get_with_policy(Ignore, 3));
+        assert!(!bf.get_with_policy(Ignore, 11));
+    }
+
+    #[test]
+    #[should_panic(expected = "position 8 is out of range for a bitflag of 8 bits")]
+    fn test_panic_set() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_with_policy(Panic, 7, true);
+        assert!(bf.get_with_policy(Panic, 7));
+        bf.set_with_policy(Panic, 8, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "position 130 is out of range for a bitflag of 128 bits")]
+    fn test_panic_get() {
+        let bf: BitFlag<u128> = BitFlag::all();
+        bf.get_with_policy(Panic, 130);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let mut bf: BitFlag<u16> = BitFlag::new();
+        bf.set_with_policy(Wrap, 17, true);
+        bf.set_with_policy(Wrap, 4, true);
+        assert_eq!(bf, 0b1_0010u16);
+        assert!(bf.get_with_policy(Wrap, 20));
+        assert!(bf.get_with_policy(Wrap, 33));
+        assert!(!bf.get_with_policy(Wrap, 16));
+    }
+
+    #[test]
+    fn test_saturate() {
+        let mut bf: BitFlag<u32> = BitFlag::new();
+        bf.set_with_policy(Saturate, 100, true);
+        bf.set_with_policy(Saturate, 0, true);
+        assert_eq!(bf, 1u32 << 31 | 1);
+        assert!(bf.get_with_policy(Saturate, 32));
+        assert!(!bf.get_with_policy(Saturate, 30));
+
+        bf.set_with_policy(Saturate, 40, false);
+        assert_eq!(bf, 1u32);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let mut bf: BitFlag<u8> = BitFlag::new();
+        bf.set_wrapping(8, true);
+        assert_eq!(bf, 1u8);
+        bf.set_wrapping(8 + 3, true);
+        assert_eq!(bf, 0b1001u8);
+        assert!(bf.get_wrapping(16));
+        assert!(bf.get_wrapping(3));
+        assert!(!bf.get_wrapping(8 + 4));
+
+        let mut bf: BitFlag<u64> = BitFlag::new();
+        bf.set_wrapping(64, true);
+        bf.set_wrapping(64 + 3, true);
+        assert_eq!(bf, 0b1001u64);
+        assert!(bf.get_wrapping(0));
+        assert!(bf.get_wrapping(64 * 5 + 3));
+        bf.set_wrapping(64 * 2, false);
+        assert_eq!(bf, 0b1000u64);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{bitflagable::BitflagAble, BitFlag};
+use serde::{de::Error, Deserialize, Deserializer};
+
+/// Deserializes a bitflag like its `Deserialize` impl but rejects values with bits set outside of
+/// `allowed`. The error names all offending positions.
+///
+/// To use it as `#[serde(deserialize_with = "...")]` wrap it into a function which passes the
+/// mask of your field.
+pub fn deserialize_masked<'de, D, T>(deserializer: D, allowed: T) -> Result<BitFlag<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: BitflagAble + Deserialize<'de>,
+{
+    let bf = BitFlag::new_with_value(T::deserialize(deserializer)?);
+    let invalid = bf - allowed;
+    if invalid.is_empty() {
+        return Ok(bf);
+    }
+
+    let positions: Vec<String> = invalid.iter_ones().map(|pos| pos.to_string()).collect();
+    Err(D::Error::custom(format!(
+        "bits at positions [{}] are not allowed",
+        positions.join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_json(json: &str, allowed: u8) -> Result<BitFlag<u8>, serde_json::Error> {
+        deserialize_masked(&mut serde_json::Deserializer::from_str(json), allowed)
+    }
+
+    #[test]
+    fn test_accepted() {
+        assert_eq!(from_json("63", 0b0011_1111).unwrap(), 63u8);
+        assert_eq!(from_json("0", 0b0011_1111).unwrap(), 0u8);
+        assert_eq!(from_json("255", 0xFF).unwrap(), 255u8);
+    }
+
+    #[test]
+    fn test_rejected() {
+        let err = from_json("64", 0b0011_1111).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("bits at positions [6] are not allowed"));
+
+        let err = from_json("193", 0b0011_1111).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("bits at positions [6, 7] are not allowed"));
+
+        // Regular deserialization errors are passed through
+        assert!(from_json("256", 0xFF).is_err());
+        assert!(from_json("\"1\"", 0xFF).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_with() {
+        fn permissions<'de, D: Deserializer<'de>>(d: D) -> Result<BitFlag<u16>, D::Error> {
+            deserialize_masked(d, 0b111)
+        }
+
+        let mut de = serde_json::Deserializer::from_str("5");
+        assert_eq!(permissions(&mut de).unwrap(), 5u16);
+
+        let mut de = serde_json::Deserializer::from_str("9");
+        assert!(permissions(&mut de).is_err());
+
+        // The plain impl stays permissive
+        let bf: BitFlag<u16> = serde_json::from_str("9").unwrap();
+        assert_eq!(bf, 9u16);
+    }
+}